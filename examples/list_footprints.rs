@@ -13,12 +13,10 @@ struct Args {
 }
 
 pub fn main() {
-  use kicad_parser::pcb_file::parse_pcb_file;
+  use kicad_parser::pcb_file::parse_pcb_file_from_path;
   let args = Args::parse();
 
-  let content = std::fs::read(args.pcb_file).unwrap();
-  let content = std::str::from_utf8(&content).expect("File doesn't contain valid utf-8");
-  let pcb = parse_pcb_file(content).unwrap();
+  let pcb = parse_pcb_file_from_path(&args.pcb_file).unwrap();
   let bounding = pcb.bounding_box();
 
   let w = bounding.width();
@@ -26,13 +24,13 @@ pub fn main() {
   println!("PCB Bounding Box: {w}x{h}");
 
   for footprint in pcb.footprints {
-    if footprint.properties.get("Value") == Some(&"Fiducial".to_string()) {
+    if footprint.property("Value") == Some("Fiducial") {
       continue;
     }
 
     let bounding = footprint.bounding_box();
-    let position = footprint.position.unwrap();
-    let name = footprint.properties.get("Value");
+    let position = footprint.position.as_ref().unwrap();
+    let name = footprint.property("Value");
     println!("Footprint bounding: {bounding} {position:?} {name:?}");
   }
 }
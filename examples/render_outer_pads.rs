@@ -1,10 +1,9 @@
-use kicad_parser::common::{BoundingBox, GetBoundingBox, Layer, PadType};
+use kicad_parser::common::{BoundingBox, GetBoundingBox, Layer};
 use std::path::PathBuf;
 
 use clap::Parser;
 
-use svg::node::element::path::Data;
-use svg::node::element::{Circle, Group, Path, Rectangle};
+use svg::node::element::{Circle, Group, Rectangle};
 use svg::{Document, Node};
 
 /// Program attempts to render the top layer to svg
@@ -21,12 +20,10 @@ struct Args {
 }
 
 pub fn main() {
-  use kicad_parser::pcb_file::parse_pcb_file;
+  use kicad_parser::pcb_file::parse_pcb_file_from_path;
   let args = Args::parse();
 
-  let content = std::fs::read(args.pcb_file.clone()).unwrap();
-  let content = std::str::from_utf8(&content).expect("File doesn't contain valid utf-8");
-  let pcb = parse_pcb_file(content).unwrap();
+  let pcb = parse_pcb_file_from_path(&args.pcb_file).unwrap();
 
   let bounding = pcb.bounding_box();
 
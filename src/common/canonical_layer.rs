@@ -0,0 +1,126 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::parser::ParserError;
+
+macro_rules! canonical_layers {
+  ($($variant: ident => $name: literal $(| $alias: literal)*,)*) => {
+    /// Layers defined by KiCad, in its canonical order: `F.Cu`, the inner copper layers by depth,
+    /// `B.Cu` and then the technical layers.
+    ///
+    /// User-defined layer names have no variant, see [`Layer::canonical`](super::Layer::canonical).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub enum CanonicalLayer {
+      $($variant,)*
+    }
+
+    impl CanonicalLayer {
+      /// Every canonical layer, in canonical order.
+      pub const ALL: &[CanonicalLayer] = &[$(CanonicalLayer::$variant,)*];
+
+      /// Returns the name KiCad writes in files, e.g. `F.SilkS`.
+      pub fn name(self) -> &'static str {
+        match self {
+          $(CanonicalLayer::$variant => $name,)*
+        }
+      }
+
+      fn from_name(name: &str) -> Option<Self> {
+        match name {
+          $($name $(| $alias)* => Some(CanonicalLayer::$variant),)*
+          _ => None,
+        }
+      }
+    }
+  };
+}
+
+canonical_layers! {
+  FCu => "F.Cu",
+  In1Cu => "In1.Cu",
+  In2Cu => "In2.Cu",
+  In3Cu => "In3.Cu",
+  In4Cu => "In4.Cu",
+  In5Cu => "In5.Cu",
+  In6Cu => "In6.Cu",
+  In7Cu => "In7.Cu",
+  In8Cu => "In8.Cu",
+  In9Cu => "In9.Cu",
+  In10Cu => "In10.Cu",
+  In11Cu => "In11.Cu",
+  In12Cu => "In12.Cu",
+  In13Cu => "In13.Cu",
+  In14Cu => "In14.Cu",
+  In15Cu => "In15.Cu",
+  In16Cu => "In16.Cu",
+  In17Cu => "In17.Cu",
+  In18Cu => "In18.Cu",
+  In19Cu => "In19.Cu",
+  In20Cu => "In20.Cu",
+  In21Cu => "In21.Cu",
+  In22Cu => "In22.Cu",
+  In23Cu => "In23.Cu",
+  In24Cu => "In24.Cu",
+  In25Cu => "In25.Cu",
+  In26Cu => "In26.Cu",
+  In27Cu => "In27.Cu",
+  In28Cu => "In28.Cu",
+  In29Cu => "In29.Cu",
+  In30Cu => "In30.Cu",
+  BCu => "B.Cu",
+  BAdhes => "B.Adhes" | "B.Adhesive",
+  FAdhes => "F.Adhes" | "F.Adhesive",
+  BPaste => "B.Paste",
+  FPaste => "F.Paste",
+  BSilkS => "B.SilkS" | "B.Silkscreen",
+  FSilkS => "F.SilkS" | "F.Silkscreen",
+  BMask => "B.Mask",
+  FMask => "F.Mask",
+  DwgsUser => "Dwgs.User" | "User.Drawings",
+  CmtsUser => "Cmts.User" | "User.Comments",
+  Eco1User => "Eco1.User" | "User.Eco1",
+  Eco2User => "Eco2.User" | "User.Eco2",
+  EdgeCuts => "Edge.Cuts",
+  Margin => "Margin",
+  BCrtYd => "B.CrtYd" | "B.Courtyard",
+  FCrtYd => "F.CrtYd" | "F.Courtyard",
+  BFab => "B.Fab",
+  FFab => "F.Fab",
+  User1 => "User.1",
+  User2 => "User.2",
+  User3 => "User.3",
+  User4 => "User.4",
+  User5 => "User.5",
+  User6 => "User.6",
+  User7 => "User.7",
+  User8 => "User.8",
+  User9 => "User.9",
+}
+
+impl CanonicalLayer {
+  /// Returns the position of this layer in the canonical sequence, 0 for `F.Cu` and 31 for
+  /// `B.Cu`.
+  pub fn rank(self) -> usize {
+    self as usize
+  }
+
+  pub fn is_copper(self) -> bool {
+    self <= CanonicalLayer::BCu
+  }
+}
+
+impl FromStr for CanonicalLayer {
+  type Err = ParserError;
+
+  /// Parses a canonical layer name, also accepting the long names of KiCad 7 and later such as
+  /// `F.Silkscreen`.
+  fn from_str(name: &str) -> Result<Self, Self::Err> {
+    CanonicalLayer::from_name(name).ok_or_else(|| ParserError::unexpected("KiCad layer name", name))
+  }
+}
+
+impl Display for CanonicalLayer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.name())
+  }
+}
@@ -0,0 +1,289 @@
+use crate::{
+  common::{FootprintText, Layer, PointList, Uuid},
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Dimension annotation measuring a distance, radius or leader (from version 6)
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Dimension {
+  /// Locked flag
+  pub locked: bool,
+  /// Kind of measurement
+  pub dimension_type: DimensionType,
+  /// Layer
+  pub layer: Layer,
+  /// Unique identifier
+  pub uuid: Uuid,
+  /// Measured points, start and end for aligned dimensions
+  pub points: PointList,
+  /// Distance of the dimension line from the measured points
+  pub height: Option<f64>,
+  /// Orthogonal dimensions: 0 for horizontal, 1 for vertical
+  pub orientation: Option<u8>,
+  /// Leader dimensions: length of the leader line
+  pub leader_length: Option<f64>,
+  /// Displayed text, with the measured value
+  pub text: Option<FootprintText>,
+  /// Formatting of the measured value
+  pub format: Option<DimensionFormat>,
+  /// Line and arrow style
+  pub style: DimensionStyle,
+}
+
+/// Dimension kinds
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DimensionType {
+  #[default]
+  Aligned,
+  Leader,
+  Center,
+  Orthogonal,
+  Radial,
+}
+
+impl DimensionType {
+  fn as_str(self) -> &'static str {
+    match self {
+      DimensionType::Aligned => "aligned",
+      DimensionType::Leader => "leader",
+      DimensionType::Center => "center",
+      DimensionType::Orthogonal => "orthogonal",
+      DimensionType::Radial => "radial",
+    }
+  }
+}
+
+impl TryFrom<SExpr> for DimensionType {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "type", "DimensionType::try_from");
+
+    Ok(match list.next_symbol()?.as_str() {
+      "aligned" => DimensionType::Aligned,
+      "leader" => DimensionType::Leader,
+      "center" => DimensionType::Center,
+      "orthogonal" => DimensionType::Orthogonal,
+      "radial" => DimensionType::Radial,
+      other => crate::error!("aligned | leader | center | orthogonal | radial", other),
+    })
+  }
+}
+
+/// Formatting of the measured value
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DimensionFormat {
+  pub prefix: String,
+  pub suffix: String,
+  /// 0: inches, 1: mils, 2: millimeters, 3: automatic
+  pub units: u8,
+  /// 0: no suffix, 1: bare suffix, 2: suffix in parentheses
+  pub units_format: u8,
+  /// Number of decimals
+  pub precision: u8,
+  /// Text shown instead of the measured value
+  pub override_value: Option<String>,
+  /// Drop trailing zeroes
+  pub suppress_zeroes: bool,
+}
+
+impl TryFrom<SExpr> for DimensionFormat {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "format", "DimensionFormat::try_from");
+
+    let mut format = DimensionFormat::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(symbol) if symbol == "suppress_zeroes" => format.suppress_zeroes = true,
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "prefix" => format.prefix = attr.read_named()?,
+          "suffix" => format.suffix = attr.read_named()?,
+          "units" => format.units = attr.read_named()?,
+          "units_format" => format.units_format = attr.read_named()?,
+          "precision" => format.precision = attr.read_named()?,
+          "override_value" => format.override_value = Some(attr.read_named()?),
+          // Since version 8 the flag is written as `(suppress_zeroes yes)`
+          "suppress_zeroes" => format.suppress_zeroes = attr.read_yes_no()?,
+          name => crate::catch_all!(name),
+        },
+        other => crate::catch_all!(other),
+      }
+    }
+
+    Ok(format)
+  }
+}
+
+impl ToSExpr for DimensionFormat {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("format")
+      .with(SExpr::named("prefix", SExpr::value(&self.prefix)))
+      .with(SExpr::named("suffix", SExpr::value(&self.suffix)))
+      .with(SExpr::named("units", self.units))
+      .with(SExpr::named("units_format", self.units_format))
+      .with(SExpr::named("precision", self.precision))
+      .with_maybe(
+        self
+          .override_value
+          .as_ref()
+          .map(|value| SExpr::named("override_value", SExpr::value(value))),
+      )
+      .with_maybe(
+        self
+          .suppress_zeroes
+          .then(|| SExpr::symbol("suppress_zeroes")),
+      )
+      .as_sexpr()
+  }
+}
+
+/// Line and arrow style of a dimension
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DimensionStyle {
+  /// Line thickness
+  pub thickness: f64,
+  pub arrow_length: f64,
+  /// 0: outside the dimension line, 1: inline, 2: manually placed
+  pub text_position_mode: u8,
+  /// Length of the extension lines past the dimension line
+  pub extension_height: Option<f64>,
+  /// Gap between the measured points and the extension lines
+  pub extension_offset: Option<f64>,
+  /// Leader dimensions: 0: no frame, 1: rectangle, 2: circle
+  pub text_frame: Option<u8>,
+  /// Keep the text parallel to the dimension line
+  pub keep_text_aligned: bool,
+}
+
+impl TryFrom<SExpr> for DimensionStyle {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "style", "DimensionStyle::try_from");
+
+    let mut style = DimensionStyle::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(symbol) if symbol == "keep_text_aligned" => style.keep_text_aligned = true,
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "thickness" => style.thickness = attr.read_named()?,
+          "arrow_length" => style.arrow_length = attr.read_named()?,
+          "text_position_mode" => style.text_position_mode = attr.read_named()?,
+          "extension_height" => style.extension_height = Some(attr.read_named()?),
+          "extension_offset" => style.extension_offset = Some(attr.read_named()?),
+          "text_frame" => style.text_frame = Some(attr.read_named()?),
+          // Since version 8 the flag is written as `(keep_text_aligned yes)`
+          "keep_text_aligned" => style.keep_text_aligned = attr.read_yes_no()?,
+          name => crate::catch_all!(name),
+        },
+        other => crate::catch_all!(other),
+      }
+    }
+
+    Ok(style)
+  }
+}
+
+impl ToSExpr for DimensionStyle {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("style")
+      .with(SExpr::named("thickness", self.thickness))
+      .with(SExpr::named("arrow_length", self.arrow_length))
+      .with(SExpr::named("text_position_mode", self.text_position_mode))
+      .with_maybe(
+        self
+          .extension_height
+          .map(|height| SExpr::named("extension_height", height)),
+      )
+      .with_maybe(
+        self
+          .extension_offset
+          .map(|offset| SExpr::named("extension_offset", offset)),
+      )
+      .with_maybe(
+        self
+          .text_frame
+          .map(|frame| SExpr::named("text_frame", frame)),
+      )
+      .with_maybe(
+        self
+          .keep_text_aligned
+          .then(|| SExpr::symbol("keep_text_aligned")),
+      )
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for Dimension {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "dimension", "Dimension::try_from");
+
+    let mut dimension = Dimension::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(symbol) if symbol == "locked" => dimension.locked = true,
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "type" => dimension.dimension_type = attr.as_sexpr_into()?,
+          // Since version 8 the flag is written as `(locked yes)`
+          "locked" => dimension.locked = attr.read_yes_no()?,
+          "layer" => dimension.layer = attr.as_sexpr_into()?,
+          "uuid" => dimension.uuid = attr.as_sexpr_into()?,
+          "pts" => dimension.points = attr.as_sexpr_into()?,
+          "height" => dimension.height = Some(attr.read_named()?),
+          "orientation" => dimension.orientation = Some(attr.read_named()?),
+          "leader_length" => dimension.leader_length = Some(attr.read_named()?),
+          "gr_text" => dimension.text = Some(attr.as_sexpr_into()?),
+          "format" => dimension.format = Some(attr.as_sexpr_into()?),
+          "style" => dimension.style = attr.as_sexpr_into()?,
+          name => crate::catch_all!(name),
+        },
+        // Files prior to version 6 lead with the measured value
+        other => crate::catch_all!(other),
+      }
+    }
+
+    Ok(dimension)
+  }
+}
+
+impl ToSExpr for Dimension {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("dimension")
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(SExpr::named(
+        "type",
+        SExpr::symbol(self.dimension_type.as_str()),
+      ))
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .with(self.points.to_sexpr())
+      .with_maybe(self.height.map(|height| SExpr::named("height", height)))
+      .with_maybe(
+        self
+          .orientation
+          .map(|orientation| SExpr::named("orientation", orientation)),
+      )
+      .with_maybe(
+        self
+          .leader_length
+          .map(|length| SExpr::named("leader_length", length)),
+      )
+      .with_maybe(self.text.as_ref().map(|text| text.to_prefixed_sexpr("gr")))
+      .with_maybe(self.format.as_ref().map(DimensionFormat::to_sexpr))
+      .with(self.style.to_sexpr())
+      .as_sexpr()
+  }
+}
@@ -0,0 +1,143 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// File stored inside the document, such as a font or a 3D model (from KiCad 8)
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EmbeddedFile {
+  /// File name, referenced as `kicad-embed://<name>`
+  pub name: String,
+  pub file_type: EmbeddedFileType,
+  /// Decoded payload. KiCad compresses it with zstd before encoding.
+  pub data: Vec<u8>,
+  /// Hash of the uncompressed content, as written by KiCad
+  pub checksum: Option<String>,
+}
+
+/// Kinds of embedded files
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum EmbeddedFileType {
+  Font,
+  Model,
+  Worksheet,
+  Datasheet,
+  #[default]
+  Other,
+}
+
+impl EmbeddedFileType {
+  fn as_str(self) -> &'static str {
+    match self {
+      EmbeddedFileType::Font => "font",
+      EmbeddedFileType::Model => "model",
+      EmbeddedFileType::Worksheet => "worksheet",
+      EmbeddedFileType::Datasheet => "datasheet",
+      EmbeddedFileType::Other => "other",
+    }
+  }
+}
+
+impl TryFrom<SExpr> for EmbeddedFileType {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "type", "EmbeddedFileType::try_from");
+
+    Ok(match list.next_symbol()?.as_str() {
+      "font" => EmbeddedFileType::Font,
+      "model" => EmbeddedFileType::Model,
+      "worksheet" => EmbeddedFileType::Worksheet,
+      "datasheet" => EmbeddedFileType::Datasheet,
+      "other" => EmbeddedFileType::Other,
+      other => crate::error!("font | model | worksheet | datasheet | other", other),
+    })
+  }
+}
+
+impl TryFrom<SExpr> for EmbeddedFile {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "file", "EmbeddedFile::try_from");
+
+    let mut file = EmbeddedFile::default();
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "name" => file.name = attr.read_named()?,
+        "type" => file.file_type = attr.as_sexpr_into()?,
+        "data" => {
+          file.data = match attr.discard(1)?.next_any()? {
+            SExpr::Blob(blob) => decode_base64(&blob)?,
+            got => return Err(ParserError::unexpected_sexpr("Blob", got)),
+          }
+        }
+        "checksum" => file.checksum = Some(attr.read_named()?),
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(file)
+  }
+}
+
+/// Decodes a blob, which KiCad splits over several lines.
+fn decode_base64(blob: &str) -> Result<Vec<u8>, ParserError> {
+  let blob: String = blob.split_whitespace().collect();
+  STANDARD
+    .decode(blob)
+    .map_err(|error| ParserError::unexpected("base64 data", error.to_string()))
+}
+
+impl ToSExpr for EmbeddedFile {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("file")
+      .with(SExpr::named("name", SExpr::value(&self.name)))
+      .with(SExpr::named("type", SExpr::symbol(self.file_type.as_str())))
+      .with(SExpr::named(
+        "data",
+        SExpr::Blob(STANDARD.encode(&self.data)),
+      ))
+      .with_maybe(
+        self
+          .checksum
+          .as_ref()
+          .map(|checksum| SExpr::named("checksum", SExpr::value(checksum))),
+      )
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for Vec<EmbeddedFile> {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "embedded_files",
+      "Vec<EmbeddedFile>::try_from"
+    );
+
+    let mut files = Self::new();
+    while let Some(file) = list.next_maybe_list()? {
+      files.push(file.as_sexpr_into()?);
+    }
+
+    Ok(files)
+  }
+}
+
+impl ToSExpr for Vec<EmbeddedFile> {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("embedded_files")
+      .with_all(self.iter().map(EmbeddedFile::to_sexpr))
+      .as_sexpr()
+  }
+}
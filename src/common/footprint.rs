@@ -1,13 +1,19 @@
-use std::collections::HashMap;
+use std::{
+  collections::{HashMap, HashSet},
+  path::PathBuf,
+};
 
 use crate::{
-  common::{BoundingBox, FootprintPolygon, GetBoundingBox, Graphic, Point, PointItem, Position},
+  common::{
+    BoundingBox, CanonicalLayer, EmbeddedFile, FootprintPolygon, FootprintTextType, GetBoundingBox,
+    Graphic, Group, Point, PointItem, Position, Zone,
+  },
   parser::ParserError,
-  sexpr::{SExpr, SExprValue},
+  sexpr::{SExpr, SExprList, SExprSymbol, ToSExpr},
 };
 
 /// Text effects for controlling text display
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TextEffects {
   /// Font settings
@@ -21,7 +27,7 @@ pub struct TextEffects {
 }
 
 /// Font definition
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Font {
   /// Font family name or "KiCad Font"
@@ -39,7 +45,7 @@ pub struct Font {
 }
 
 /// Text justification options
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Justify {
   /// Horizontal justification
@@ -64,11 +70,160 @@ pub enum VerticalJustify {
   Center,
 }
 
+impl TryFrom<SExpr> for TextEffects {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "effects", "TextEffects::try_from");
+
+    let mut effects = TextEffects::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(s) if s == "hide" => effects.hide = true,
+
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "font" => effects.font = attr.as_sexpr_into()?,
+          "hide" => effects.hide = attr.read_yes_no()?,
+          "justify" => {
+            // Justification symbols may appear in any combination and order
+            let mut justify = Justify::default();
+            attr.discard(1)?;
+            while let Some(symbol) = attr.next_maybe_symbol()? {
+              match symbol.as_str() {
+                "left" => justify.horizontal = Some(HorizontalJustify::Left),
+                "right" => justify.horizontal = Some(HorizontalJustify::Right),
+                "top" => justify.vertical = Some(VerticalJustify::Top),
+                "bottom" => justify.vertical = Some(VerticalJustify::Bottom),
+                "mirror" => effects.mirror = true,
+                other => crate::catch_all!(other),
+              }
+            }
+            effects.justify = Some(justify);
+          }
+
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(effects)
+  }
+}
+
+impl TryFrom<SExpr> for Font {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "font", "Font::try_from");
+
+    let mut font = Font::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(s) if s == "bold" => font.bold = true,
+        SExpr::Symbol(s) if s == "italic" => font.italic = true,
+
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "size" => {
+            let height = attr.discard(1)?.next_into()?;
+            let width = attr.next_into()?;
+            font.size = (height, width);
+          }
+          "thickness" => font.thickness = attr.read_named()?,
+          "face" => font.face = Some(attr.read_named()?),
+          "line_spacing" => font.line_spacing = Some(attr.read_named()?),
+          // Since version 8 the flags are written as `(bold yes)`
+          "bold" => font.bold = attr.read_yes_no()?,
+          "italic" => font.italic = attr.read_yes_no()?,
+
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(font)
+  }
+}
+
+impl ToSExpr for TextEffects {
+  fn to_sexpr(&self) -> SExpr {
+    let justify = (self.justify.is_some() || self.mirror).then(|| {
+      let justify = self.justify.clone().unwrap_or_default();
+      let horizontal = justify.horizontal.and_then(|h| match h {
+        HorizontalJustify::Left => Some("left"),
+        HorizontalJustify::Right => Some("right"),
+        HorizontalJustify::Center => None,
+      });
+      let vertical = justify.vertical.and_then(|v| match v {
+        VerticalJustify::Top => Some("top"),
+        VerticalJustify::Bottom => Some("bottom"),
+        VerticalJustify::Center => None,
+      });
+
+      SExprList::named("justify")
+        .with_maybe(horizontal.map(SExpr::symbol))
+        .with_maybe(vertical.map(SExpr::symbol))
+        .with_maybe(self.mirror.then(|| SExpr::symbol("mirror")))
+        .as_sexpr()
+    });
+
+    SExprList::named("effects")
+      .with(self.font.to_sexpr())
+      .with_maybe(justify)
+      .with_maybe(self.hide.then(|| SExpr::named("hide", SExpr::yes_no(true))))
+      .as_sexpr()
+  }
+}
+
+impl ToSExpr for Font {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("font")
+      .with_maybe(
+        self
+          .face
+          .clone()
+          .map(|face| SExpr::named("face", SExpr::value(face))),
+      )
+      .with(
+        SExprList::named("size")
+          .with(self.size.0.into())
+          .with(self.size.1.into())
+          .as_sexpr(),
+      )
+      .with_maybe((self.thickness != 0.0).then(|| SExpr::named("thickness", self.thickness)))
+      .with_maybe(self.bold.then(|| SExpr::symbol("bold")))
+      .with_maybe(self.italic.then(|| SExpr::symbol("italic")))
+      .with_maybe(
+        self
+          .line_spacing
+          .map(|spacing| SExpr::named("line_spacing", spacing)),
+      )
+      .as_sexpr()
+  }
+}
+
 /// Universally unique identifier
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Uuid(pub String);
 
+#[cfg(feature = "uuid")]
+impl Uuid {
+  /// Generates a new random (version 4) identifier.
+  pub fn new_v4() -> Self {
+    Uuid(uuid::Uuid::new_v4().to_string())
+  }
+
+  /// Parses the identifier, `None` when it is empty or malformed. Parsed values compare equal
+  /// regardless of case and hyphenation.
+  pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+    uuid::Uuid::try_parse(&self.0).ok()
+  }
+}
+
 impl TryFrom<SExpr> for Uuid {
   type Error = ParserError;
 
@@ -80,25 +235,128 @@ impl TryFrom<SExpr> for Uuid {
     if uuid_str.is_empty() {
       return Err(ParserError::unexpected("Non-empty UUID", uuid_str));
     }
+    #[cfg(feature = "uuid")]
+    if crate::parser::parse_options().strict && uuid::Uuid::try_parse(&uuid_str).is_err() {
+      return Err(ParserError::unexpected("valid UUID", uuid_str).add_context(crate::context!()));
+    }
 
     list.expect_end()?;
     Ok(Uuid(uuid_str))
   }
 }
 
+impl ToSExpr for Uuid {
+  fn to_sexpr(&self) -> SExpr {
+    SExpr::named("uuid", SExpr::value(&self.0))
+  }
+}
+
+impl Uuid {
+  /// Returns the `uuid` token, or `None` for items read from files that predate UUIDs.
+  pub(crate) fn to_sexpr_if_set(&self) -> Option<SExpr> {
+    (!self.0.is_empty()).then(|| self.to_sexpr())
+  }
+}
+
 /// Canonical layer names
-#[derive(Default, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+///
+/// With serde, a layer is written as its name, e.g. `"F.Cu"`, or as a struct when knocked out.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Layer {
   pub layer_name: String,
   /// Inverts bbox outline with content outline
   pub knockout: bool,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Layer {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeStruct;
+
+    if !self.knockout {
+      return serializer.serialize_str(&self.layer_name);
+    }
+
+    let mut layer = serializer.serialize_struct("Layer", 2)?;
+    layer.serialize_field("layer_name", &self.layer_name)?;
+    layer.serialize_field("knockout", &self.knockout)?;
+    layer.end()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Layer {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+      Name(String),
+      Full {
+        layer_name: String,
+        #[serde(default)]
+        knockout: bool,
+      },
+    }
+
+    Ok(match Repr::deserialize(deserializer)? {
+      Repr::Name(layer_name) => Layer::new(layer_name),
+      Repr::Full {
+        layer_name,
+        knockout,
+      } => Layer {
+        layer_name,
+        knockout,
+      },
+    })
+  }
+}
+
 impl Layer {
+  /// Creates a layer from its name, e.g. `F.Cu`, without knockout.
+  pub fn new(name: impl Into<String>) -> Self {
+    Layer {
+      layer_name: name.into(),
+      knockout: false,
+    }
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.layer_name
+  }
+
   pub fn is_back(&self) -> bool {
     self.layer_name.starts_with("B.")
   }
+
+  /// Returns the KiCad-defined layer this name refers to, or `None` for user-defined names.
+  pub fn canonical(&self) -> Option<CanonicalLayer> {
+    self.layer_name.parse().ok()
+  }
+
+  /// Returns the position of this layer in KiCad's canonical layer sequence: `F.Cu`, the inner
+  /// copper layers by depth, `B.Cu` and then the technical layers.
+  ///
+  /// Returns `None` for layer names KiCad does not define.
+  pub fn canonical_rank(&self) -> Option<usize> {
+    self.canonical().map(CanonicalLayer::rank)
+  }
+}
+
+impl Ord for Layer {
+  /// Orders layers canonically, with unknown layers last sorted by name.
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    let rank = |layer: &Layer| layer.canonical_rank().unwrap_or(usize::MAX);
+    rank(self)
+      .cmp(&rank(other))
+      .then_with(|| self.layer_name.cmp(&other.layer_name))
+      .then_with(|| self.knockout.cmp(&other.knockout))
+  }
+}
+
+impl PartialOrd for Layer {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
 }
 
 impl TryFrom<SExpr> for Layer {
@@ -108,7 +366,7 @@ impl TryFrom<SExpr> for Layer {
     let mut list = value.as_list()?;
     crate::expect_eq!(list.next_symbol()?, "layer", "Layer::try_from");
 
-    let value: SExprValue = list.next_into()?;
+    let value = layer_name(list.next_any()?)?;
 
     let mut knockout = false;
     if let Ok(layer_attrib) = list.next_symbol()
@@ -119,12 +377,43 @@ impl TryFrom<SExpr> for Layer {
     list.expect_end()?;
 
     Ok(Self {
-      layer_name: value.to_string(),
+      layer_name: value,
       knockout,
     })
   }
 }
 
+impl ToSExpr for Layer {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("layer")
+      .with(SExpr::value(&self.layer_name))
+      .with_maybe(self.knockout.then(|| SExpr::symbol("knockout")))
+      .as_sexpr()
+  }
+}
+
+/// Writes a list of layer names such as `(layers "F.Cu" "F.Mask")`.
+pub(crate) fn layer_list(name: &str, layers: &[Layer]) -> SExpr {
+  SExprList::named(name)
+    .with_all(layers.iter().map(|layer| SExpr::value(&layer.layer_name)))
+    .as_sexpr()
+}
+
+impl ToSExpr for Vec<Layer> {
+  fn to_sexpr(&self) -> SExpr {
+    layer_list("layers", self)
+  }
+}
+
+/// Reads a layer name, which older files write as a bare symbol instead of a quoted string.
+fn layer_name(expr: SExpr) -> Result<String, ParserError> {
+  match expr {
+    SExpr::Value(value) => Ok(value.0),
+    SExpr::Symbol(symbol) => Ok(symbol.0),
+    got => Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+  }
+}
+
 impl From<&str> for Layer {
   fn from(value: &str) -> Self {
     Layer {
@@ -142,9 +431,9 @@ impl TryFrom<SExpr> for Vec<Layer> {
     crate::expect_eq!(list.next_symbol()?, "layers", "Layer::try_from");
 
     let mut out = Self::new();
-    while let Some(value) = list.next_maybe_into::<SExprValue>()? {
+    while let Some(value) = list.next_maybe() {
       out.push(Layer {
-        layer_name: value.to_string(),
+        layer_name: layer_name(value)?,
         knockout: false,
       });
     }
@@ -159,6 +448,12 @@ impl PartialEq<str> for Layer {
   }
 }
 
+impl PartialEq<&str> for Layer {
+  fn eq(&self, other: &&str) -> bool {
+    self.layer_name == *other
+  }
+}
+
 /// Zone connection types
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -169,6 +464,23 @@ pub enum ZoneConnect {
   Thermal = 1,
   /// Pad connected to zone using solid fill
   Solid = 2,
+  /// Only through-hole pads connected to zone using thermal relief
+  ThermalThroughHole = 3,
+}
+
+impl TryFrom<SExpr> for ZoneConnect {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let connect: u8 = value.try_into()?;
+    match connect {
+      0 => Ok(ZoneConnect::None),
+      1 => Ok(ZoneConnect::Thermal),
+      2 => Ok(ZoneConnect::Solid),
+      3 => Ok(ZoneConnect::ThermalThroughHole),
+      found => crate::error!("Valid zone connection", found),
+    }
+  }
 }
 
 /// Footprint attributes
@@ -185,6 +497,12 @@ pub struct FootprintAttributes {
   pub exclude_from_bom: bool,
   /// Do not populate this footprint in the BOM
   pub do_not_populate: bool,
+  /// Allow solder mask bridges between pads (from version 8)
+  pub allow_soldermask_bridges: bool,
+  /// Skip the missing courtyard check (from version 8)
+  pub allow_missing_courtyard: bool,
+  /// Scale factor given as `(scale ..)`
+  pub scale: Option<f64>,
 }
 
 impl TryFrom<SExpr> for FootprintAttributes {
@@ -195,15 +513,24 @@ impl TryFrom<SExpr> for FootprintAttributes {
     crate::expect_eq!(list.next_symbol()?, "attr", "FootprintAttributes::try_from");
 
     let mut attributes = Self::default();
-    while let Some(next) = list.next_maybe_symbol()? {
-      match next.as_str() {
-        "smd" => attributes.footprint_type = FootprintType::Smd,
-        "through_hole" => attributes.footprint_type = FootprintType::ThroughHole,
-        "board_only" => attributes.board_only = true,
-        "exclude_from_pos_files" => attributes.exclude_from_pos_files = true,
-        "exclude_from_bom" => attributes.exclude_from_bom = true,
-        "dnp" | "do_not_populate" => attributes.do_not_populate = true,
-        name => crate::catch_all!(name),
+    while let Some(next) = list.next_maybe() {
+      match next {
+        SExpr::Symbol(flag) => match flag.as_str() {
+          "smd" => attributes.footprint_type = FootprintType::Smd,
+          "through_hole" => attributes.footprint_type = FootprintType::ThroughHole,
+          "board_only" => attributes.board_only = true,
+          "exclude_from_pos_files" => attributes.exclude_from_pos_files = true,
+          "exclude_from_bom" => attributes.exclude_from_bom = true,
+          "dnp" | "do_not_populate" => attributes.do_not_populate = true,
+          "allow_soldermask_bridges" => attributes.allow_soldermask_bridges = true,
+          "allow_missing_courtyard" => attributes.allow_missing_courtyard = true,
+          name => crate::catch_all!(name),
+        },
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "scale" => attributes.scale = Some(attr.read_named()?),
+          name => crate::catch_all!(name),
+        },
+        other => crate::catch_all!(other),
       }
     }
 
@@ -211,6 +538,43 @@ impl TryFrom<SExpr> for FootprintAttributes {
   }
 }
 
+impl ToSExpr for FootprintAttributes {
+  fn to_sexpr(&self) -> SExpr {
+    let footprint_type = match self.footprint_type {
+      FootprintType::Smd => Some("smd"),
+      FootprintType::ThroughHole => Some("through_hole"),
+      FootprintType::Unspecified => None,
+    };
+
+    SExprList::named("attr")
+      .with_maybe(footprint_type.map(SExpr::symbol))
+      .with_maybe(self.board_only.then(|| SExpr::symbol("board_only")))
+      .with_maybe(
+        self
+          .exclude_from_pos_files
+          .then(|| SExpr::symbol("exclude_from_pos_files")),
+      )
+      .with_maybe(
+        self
+          .exclude_from_bom
+          .then(|| SExpr::symbol("exclude_from_bom")),
+      )
+      .with_maybe(self.do_not_populate.then(|| SExpr::symbol("dnp")))
+      .with_maybe(
+        self
+          .allow_soldermask_bridges
+          .then(|| SExpr::symbol("allow_soldermask_bridges")),
+      )
+      .with_maybe(
+        self
+          .allow_missing_courtyard
+          .then(|| SExpr::symbol("allow_missing_courtyard")),
+      )
+      .with_maybe(self.scale.map(|scale| SExpr::named("scale", scale)))
+      .as_sexpr()
+  }
+}
+
 /// Footprint type classification
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -235,6 +599,193 @@ pub struct Model3D {
   pub rotation: (f64, f64, f64),
 }
 
+impl Model3D {
+  /// Returns the model path with `${VAR}` tokens such as `${KICAD7_3DMODEL_DIR}` replaced by
+  /// their value in `vars`. Tokens of unknown variables are left verbatim.
+  pub fn resolved_path(&self, vars: &HashMap<String, String>) -> PathBuf {
+    let mut out = String::with_capacity(self.file.len());
+    let mut rest = self.file.as_str();
+    while let Some(start) = rest.find("${") {
+      out.push_str(&rest[..start]);
+      rest = &rest[start..];
+
+      let Some(end) = rest.find('}') else {
+        break;
+      };
+
+      match vars.get(&rest[2..end]) {
+        Some(value) => out.push_str(value),
+        None => out.push_str(&rest[..=end]),
+      }
+      rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+
+    PathBuf::from(out)
+  }
+}
+
+impl TryFrom<SExpr> for Model3D {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "model", "Model3D::try_from");
+
+    // Older files may leave the path unquoted
+    let file = match list.next_any()? {
+      SExpr::Value(value) => value.0,
+      SExpr::Symbol(symbol) => symbol.0,
+      got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+    };
+
+    let mut model = Model3D {
+      file,
+      position: (0.0, 0.0, 0.0),
+      scale: (1.0, 1.0, 1.0),
+      rotation: (0.0, 0.0, 0.0),
+    };
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "offset" => model.position = read_xyz(&mut attr)?,
+        // Files prior to version 6 give the offset in inches
+        "at" => {
+          let (x, y, z) = read_xyz(&mut attr)?;
+          model.position = (x * 25.4, y * 25.4, z * 25.4);
+        }
+        "scale" => model.scale = read_xyz(&mut attr)?,
+        "rotate" => model.rotation = read_xyz(&mut attr)?,
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(model)
+  }
+}
+
+/// Reads a wrapped coordinate triple such as `(offset (xyz 1 2 3))`.
+fn read_xyz(list: &mut SExprList) -> Result<(f64, f64, f64), ParserError> {
+  let mut xyz = list.discard(1)?.next_list()?;
+  list.expect_end()?;
+
+  crate::expect_eq!(xyz.next_symbol()?, "xyz", "read_xyz");
+  let x = xyz.next_into()?;
+  let y = xyz.next_into()?;
+  let z = xyz.next_into()?;
+  xyz.expect_end()?;
+
+  Ok((x, y, z))
+}
+
+impl ToSExpr for Model3D {
+  fn to_sexpr(&self) -> SExpr {
+    let xyz = |name: &str, (x, y, z): (f64, f64, f64)| {
+      let xyz = SExprList::named("xyz")
+        .with(x.into())
+        .with(y.into())
+        .with(z.into());
+      SExpr::named(name, xyz.as_sexpr())
+    };
+
+    SExprList::named("model")
+      .with(SExpr::value(&self.file))
+      .with(xyz("offset", self.position))
+      .with(xyz("scale", self.scale))
+      .with(xyz("rotate", self.rotation))
+      .as_sexpr()
+  }
+}
+
+/// Footprint property such as `Reference` or `Value`.
+///
+/// Since version 7 the property also carries the placement of its text; the optional fields are
+/// `None` for plain key/value properties.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FootprintProperty {
+  /// Property name
+  pub key: String,
+  /// Property value
+  pub value: String,
+  /// Text position and angle
+  pub position: Option<Position>,
+  /// Unlocked orientation flag
+  pub unlocked: bool,
+  /// Text layer
+  pub layer: Option<Layer>,
+  /// Hidden flag
+  pub hide: bool,
+  /// Unique identifier
+  pub uuid: Option<Uuid>,
+  /// Text effects
+  pub effects: Option<TextEffects>,
+}
+
+impl TryFrom<SExpr> for FootprintProperty {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "property",
+      "FootprintProperty::try_from"
+    );
+
+    let mut property = FootprintProperty {
+      key: match list.next_any()? {
+        SExpr::Value(value) => value.0,
+        SExpr::Symbol(symbol) => symbol.0,
+        got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+      },
+      value: list.next_into()?,
+      ..Default::default()
+    };
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "at" => property.position = Some(attr.as_sexpr_into()?),
+        "unlocked" => property.unlocked = attr.read_yes_no()?,
+        "layer" => property.layer = Some(attr.as_sexpr_into()?),
+        "hide" => property.hide = attr.read_yes_no()?,
+        "uuid" => property.uuid = Some(attr.as_sexpr_into()?),
+        "effects" => {
+          let effects: TextEffects = attr.as_sexpr_into()?;
+          property.hide |= effects.hide;
+          property.effects = Some(effects);
+        }
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(property)
+  }
+}
+
+impl ToSExpr for FootprintProperty {
+  fn to_sexpr(&self) -> SExpr {
+    let effects_hide = self.effects.as_ref().is_some_and(|effects| effects.hide);
+
+    SExprList::named("property")
+      .with(SExpr::value(&self.key))
+      .with(SExpr::value(&self.value))
+      .with_maybe(self.position.as_ref().map(Position::to_sexpr))
+      .with_maybe(
+        self
+          .unlocked
+          .then(|| SExpr::named("unlocked", SExpr::yes_no(true))),
+      )
+      .with_maybe(self.layer.as_ref().map(Layer::to_sexpr))
+      // Hidden text is written through the effects when they carry the flag
+      .with_maybe((self.hide && !effects_hide).then(|| SExpr::named("hide", SExpr::yes_no(true))))
+      .with_maybe(self.uuid.as_ref().map(Uuid::to_sexpr))
+      .with_maybe(self.effects.as_ref().map(TextEffects::to_sexpr))
+      .as_sexpr()
+  }
+}
+
 /// Main footprint definition
 /// Prior to version 6, this was called `module`
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -242,6 +793,12 @@ pub struct Model3D {
 pub struct Footprint {
   /// Library link (for board footprints)
   pub library_link: Option<String>,
+  /// File format version, written in footprint files and, since version 8, board footprints
+  pub version: Option<String>,
+  /// Generating application, e.g. `pcbnew`
+  pub generator: Option<String>,
+  /// Version of the generating application (from version 8)
+  pub generator_version: Option<String>,
   /// Locked flag - cannot be edited
   pub locked: bool,
   /// Placed flag - indicates footprint placement status
@@ -258,8 +815,8 @@ pub struct Footprint {
   pub tags: Option<String>,
   /// Description
   pub description: Option<String>,
-  /// Properties
-  pub properties: HashMap<String, String>,
+  /// Properties, in file order
+  pub properties: Vec<FootprintProperty>,
   /// Hierarchical path (board footprints only)
   pub path: Option<String>,
   /// Autoplace cost for 90° rotation
@@ -290,45 +847,294 @@ pub struct Footprint {
   pub graphics: Vec<Graphic>,
   /// Pads
   pub pads: Vec<Pad>,
-  /// Keep-out zones
+  /// Zones, typically keep-out rule areas
   pub zones: Vec<Zone>,
   /// Grouped objects
   pub groups: Vec<Group>,
   /// 3D models
   pub models: Vec<Model3D>,
+  /// Whether the fonts used by the footprint are stored in it, written since version 9
+  pub embedded_fonts: Option<bool>,
+  /// Fonts, 3D models and other files stored in the footprint
+  pub embedded_files: Vec<EmbeddedFile>,
+  /// Unmodeled items, kept when parsing with
+  /// [`collect_unknown`](crate::parser::ParseOptions::collect_unknown)
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub unknown: Vec<SExpr>,
 }
 
 impl Footprint {
+  /// Returns the library nickname part of the library link, e.g. `Resistor_SMD` for
+  /// `Resistor_SMD:R_0402`.
+  pub fn library_nickname(&self) -> Option<&str> {
+    let (nickname, _) = self.library_link.as_deref()?.split_once(':')?;
+    Some(nickname)
+  }
+
+  /// Returns the footprint name part of the library link, e.g. `R_0402` for
+  /// `Resistor_SMD:R_0402`. Links without a nickname are returned as-is.
+  pub fn library_name(&self) -> Option<&str> {
+    let link = self.library_link.as_deref()?;
+    Some(link.split_once(':').map_or(link, |(_, name)| name))
+  }
+
+  /// Counts the unique pad numbers of this footprint, for comparing against a symbol's pin count.
+  ///
+  /// Pads sharing a number (such as thermal pad splits) count once and unnumbered mechanical
+  /// pads are excluded.
+  pub fn distinct_pad_numbers(&self) -> usize {
+    self
+      .pads
+      .iter()
+      .filter(|pad| !pad.number.is_empty())
+      .map(|pad| pad.number.as_str())
+      .collect::<HashSet<_>>()
+      .len()
+  }
+
+  /// Reports pairs of pad indices that are stacked on top of each other: same position, size and
+  /// number. Such duplicates are usually a footprint generator mistake.
+  pub fn coincident_pads(&self) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for (i, a) in self.pads.iter().enumerate() {
+      for (j, b) in self.pads.iter().enumerate().skip(i + 1) {
+        if a.position.x == b.position.x
+          && a.position.y == b.position.y
+          && a.size == b.size
+          && a.number == b.number
+        {
+          out.push((i, j));
+        }
+      }
+    }
+    out
+  }
+
+  /// Clones the footprint to a new absolute position, for placing arrays of components.
+  ///
+  /// The footprint and every item inside it (pads, graphics, properties, zones and groups)
+  /// receive freshly generated UUIDs so the copy can live on the same board as the original.
+  /// Group members are updated to the new UUIDs.
+  #[cfg(feature = "uuid")]
+  pub fn clone_at(&self, position: Position) -> Footprint {
+    let mut footprint = self.clone();
+    footprint.position = Some(position);
+    footprint.uuid = Some(Uuid::new_v4());
+
+    let mut renamed = HashMap::new();
+    let mut regenerate = |uuid: &mut Uuid| {
+      let fresh = Uuid::new_v4();
+      renamed.insert(std::mem::replace(uuid, fresh.clone()), fresh);
+    };
+    for pad in &mut footprint.pads {
+      regenerate(&mut pad.uuid);
+    }
+    for graphic in &mut footprint.graphics {
+      regenerate(graphic.uuid_mut());
+    }
+    for uuid in footprint
+      .properties
+      .iter_mut()
+      .filter_map(|property| property.uuid.as_mut())
+    {
+      regenerate(uuid);
+    }
+    for zone in &mut footprint.zones {
+      regenerate(&mut zone.uuid);
+    }
+    for group in &mut footprint.groups {
+      regenerate(&mut group.uuid);
+    }
+
+    for group in &mut footprint.groups {
+      for member in &mut group.members {
+        if let Some(fresh) = renamed.get(member) {
+          *member = fresh.clone();
+        }
+      }
+    }
+
+    footprint
+  }
+
+  /// Returns the value of the property named `key`, e.g. `Value` or `Datasheet`.
+  pub fn property(&self, key: &str) -> Option<&str> {
+    self
+      .properties
+      .iter()
+      .find(|property| property.key == key)
+      .map(|property| property.value.as_str())
+  }
+
+  /// Returns the reference designator such as `R1`, from the `Reference` property or, in files
+  /// prior to version 8, the reference text.
+  pub fn reference(&self) -> Option<&str> {
+    self.property("Reference").or_else(|| {
+      self.graphics.iter().find_map(|graphic| match graphic {
+        Graphic::Text(text) if text.text_type == FootprintTextType::Reference => {
+          Some(text.text.as_str())
+        }
+        _ => None,
+      })
+    })
+  }
+
+  /// Returns the value such as `10k`, from the `Value` property or, in files prior to version 8,
+  /// the value text.
+  pub fn value(&self) -> Option<&str> {
+    self.property("Value").or_else(|| {
+      self.graphics.iter().find_map(|graphic| match graphic {
+        Graphic::Text(text) if text.text_type == FootprintTextType::Value => {
+          Some(text.text.as_str())
+        }
+        _ => None,
+      })
+    })
+  }
+
+  /// Returns the graphic items moved to board coordinates, applying the rotation and translation
+  /// of the footprint position, see [`Graphic::transformed`].
+  pub fn graphics_in_board_space(&self) -> Vec<Graphic> {
+    let placement = self.position.clone().unwrap_or_default();
+    self
+      .graphics
+      .iter()
+      .map(|graphic| graphic.transformed(&placement))
+      .collect()
+  }
+
+  /// Iterates over the graphic items drawn on the given layer, e.g. `F.SilkS` or `F.CrtYd`.
+  pub fn graphics_on_layer<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = &'a Graphic> {
+    self
+      .graphics
+      .iter()
+      .filter(move |graphic| graphic.layer() == layer)
+  }
+
+  /// Sums the solder paste coverage of the SMD pads, in mm², for paste volume estimates.
+  ///
+  /// Each pad on a paste layer is grown or shrunk by its paste margin and margin ratio, falling
+  /// back to the footprint overrides when the pad has none.
+  pub fn paste_area(&self) -> f64 {
+    self
+      .pads
+      .iter()
+      .filter(|pad| pad.pad_type == PadType::Smd)
+      .filter(|pad| {
+        pad
+          .layers
+          .iter()
+          .any(|layer| layer.layer_name.ends_with(".Paste"))
+      })
+      .map(|pad| {
+        let margin = pad
+          .solder_paste_margin
+          .or(self.solder_paste_margin)
+          .unwrap_or(0.0);
+        let ratio = pad
+          .solder_paste_margin_ratio
+          .or(self.solder_paste_ratio)
+          .unwrap_or(0.0);
+        // Like KiCad, the ratio applies to each axis separately
+        let (w, h) = pad.size;
+        pad.area_with_margins(margin + ratio * w, margin + ratio * h)
+      })
+      .sum()
+  }
+
+  /// Returns the footprint attributes, or defaults with the footprint type inferred from the
+  /// pads when the footprint has no `attr` token.
+  pub fn attributes_or_default(&self) -> FootprintAttributes {
+    self
+      .attributes
+      .clone()
+      .unwrap_or_else(|| FootprintAttributes {
+        footprint_type: self.inferred_footprint_type(),
+        ..Default::default()
+      })
+  }
+
+  /// Returns the footprint type from the `attr` token, inferring it from the pads when the token
+  /// is missing or does not name a type.
+  pub fn footprint_type(&self) -> FootprintType {
+    match self.attributes.as_ref().map(|attr| &attr.footprint_type) {
+      Some(FootprintType::Unspecified) | None => self.inferred_footprint_type(),
+      Some(footprint_type) => footprint_type.clone(),
+    }
+  }
+
+  /// Whether the footprint is surface mounted, see [`Footprint::footprint_type`].
+  pub fn is_smd(&self) -> bool {
+    self.footprint_type() == FootprintType::Smd
+  }
+
+  /// Whether the footprint is through-hole, see [`Footprint::footprint_type`].
+  pub fn is_tht(&self) -> bool {
+    self.footprint_type() == FootprintType::ThroughHole
+  }
+
+  /// Infers the footprint type from the pads: any plated through-hole pad makes it through-hole,
+  /// otherwise any SMD pad makes it SMD.
+  fn inferred_footprint_type(&self) -> FootprintType {
+    if self
+      .pads
+      .iter()
+      .any(|pad| pad.pad_type == PadType::ThroughHole)
+    {
+      FootprintType::ThroughHole
+    } else if self.pads.iter().any(|pad| pad.pad_type == PadType::Smd) {
+      FootprintType::Smd
+    } else {
+      FootprintType::Unspecified
+    }
+  }
+
+  /// Returns the board space extent of the pads on `layer`, applying the rotation and translation
+  /// of the footprint position. Pad angles are already absolute, so only the pad anchors move.
   pub fn pad_bounding_box(&self, layer: &Layer) -> BoundingBox {
+    let placement = self.position.clone().unwrap_or_default();
     let mut bbox = BoundingBox::default();
 
     for pad in &self.pads {
       // Check if the pad is defined on the requested layer
       if pad.layers.contains(layer) {
-        bbox.envelop(&pad.bounding_box());
+        let placed = Pad {
+          position: placement.transform_position(&pad.position),
+          ..pad.clone()
+        };
+        bbox.envelop(&placed.bounding_box());
       }
     }
 
-    // Apply the footprint's global placement (same logic as in
-    // Footprint::bounding_box())
-    let position = self.position.as_ref();
-    let x = position.map_or(0.0, |p| p.x);
-    let y = position.map_or(0.0, |p| p.y);
-    bbox.move_by(x, y);
-
     bbox
   }
 }
 
+/// Parses a footprint library file (`.kicad_mod`), whose root is the `footprint` list itself, or
+/// `module` before version 6.
+pub fn parse_footprint_file(input: &str) -> Result<Footprint, ParserError> {
+  crate::parser::parse_document(input, "valid KiCad footprint file")
+}
+
 impl TryFrom<SExpr> for Footprint {
   type Error = ParserError;
 
   fn try_from(value: SExpr) -> Result<Self, Self::Error> {
     let mut list = value.as_list()?;
-    crate::expect_eq!(list.next_symbol()?, "footprint", "Footprint::try_from");
+    let name = list.next_symbol()?;
+    if name != "footprint" && name != "module" {
+      crate::error!("footprint | module", name);
+    }
 
     let mut footprint = Footprint::default();
 
+    // KiCad 5 leaves the library link of a `module` unquoted
+    if let Some(SExpr::Symbol(symbol)) = list.peek_maybe()
+      && !matches!(symbol.as_str(), "locked" | "placed")
+    {
+      footprint.library_link = Some(list.next_symbol()?.0);
+    }
+
     while let Some(next) = list.next_maybe() {
       match next {
         SExpr::Value(value) => footprint.library_link = Some(value.0),
@@ -336,9 +1142,29 @@ impl TryFrom<SExpr> for Footprint {
         SExpr::Symbol(symbol) if symbol == "placed" => footprint.placed = true,
 
         SExpr::List(mut list) => match list.peek_name()? {
+          "version" => {
+            let version: i64 = list.discard(1)?.next_into()?;
+            footprint.version = Some(version.to_string());
+          }
+          // Before version 7 the generator was written as a bare symbol
+          "generator" => {
+            footprint.generator = Some(match list.discard(1)?.next_any()? {
+              SExpr::Value(value) => value.0,
+              SExpr::Symbol(symbol) => symbol.0,
+              got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+            })
+          }
+          "generator_version" => footprint.generator_version = Some(list.discard(1)?.next_into()?),
           "uuid" => footprint.uuid = Some(list.as_sexpr_into()?),
           "layer" => footprint.layer = list.as_sexpr_into()?,
-          // TEDIT?
+          // Last edit timestamp, written until version 6
+          "tedit" => {
+            footprint.tedit = Some(match list.discard(1)?.next_any()? {
+              SExpr::Symbol(symbol) => symbol.0,
+              SExpr::Int(timestamp) => timestamp.to_string(),
+              got => return Err(ParserError::unexpected_sexpr("Symbol or Int", got)),
+            })
+          }
           "descr" | "description" => footprint.description = Some(list.discard(1)?.next_into()?),
           "at" => footprint.position = Some(list.as_sexpr_into()?),
           "tags" => footprint.tags = Some(list.discard(1)?.next_into()?),
@@ -348,24 +1174,60 @@ impl TryFrom<SExpr> for Footprint {
           // "sheetname" => footprint.name = Some(list.discard(1)?.next_into()?),
           // "sheetfile" => footprint.sheetfile = Some(list.discard(1)?.next_into()?),
           "attr" => footprint.attributes = Some(list.as_sexpr_into()?),
+          "solder_mask_margin" => footprint.solder_mask_margin = Some(list.read_named()?),
+          "solder_paste_margin" => footprint.solder_paste_margin = Some(list.read_named()?),
+          "solder_paste_ratio" | "solder_paste_margin_ratio" => {
+            footprint.solder_paste_ratio = Some(list.read_named()?)
+          }
+          "autoplace_cost90" => footprint.autoplace_cost90 = Some(list.read_named()?),
+          "autoplace_cost180" => footprint.autoplace_cost180 = Some(list.read_named()?),
+          "clearance" => footprint.clearance = Some(list.read_named()?),
+          "zone_connect" => footprint.zone_connect = Some(list.read_named()?),
+          "thermal_width" | "thermal_bridge_width" => {
+            footprint.thermal_width = Some(list.read_named()?)
+          }
+          "thermal_gap" => footprint.thermal_gap = Some(list.read_named()?),
+          "private_layers" => {
+            list.discard(1)?;
+            while let Some(value) = list.next_maybe() {
+              footprint.private_layers.push(Layer {
+                layer_name: layer_name(value)?,
+                knockout: false,
+              });
+            }
+          }
+          // Each group is a comma separated list of pad numbers, e.g. `"1, 2"`
+          "net_tie_pad_groups" => {
+            list.discard(1)?;
+            while let Some(group) = list.next_maybe() {
+              let group: String = group.try_into()?;
+              footprint.net_tie_pad_groups.push(
+                group
+                  .split(',')
+                  .map(str::trim)
+                  .filter(|pad| !pad.is_empty())
+                  .map(String::from)
+                  .collect(),
+              );
+            }
+          }
           "pad" => footprint.pads.push(list.as_sexpr_into()?),
+          "model" => footprint.models.push(list.as_sexpr_into()?),
+          "zone" => footprint.zones.push(list.as_sexpr_into()?),
+          "group" => footprint.groups.push(list.as_sexpr_into()?),
+          "embedded_fonts" => footprint.embedded_fonts = Some(list.read_yes_no()?),
+          "embedded_files" => footprint.embedded_files = list.as_sexpr_into()?,
 
-          "property" => {
-            list.discard(1)?; // Discard the "property" keyword
-            let key = match list.next_any()? {
-              SExpr::Value(value) => value.0,
-              SExpr::Symbol(symbol) => symbol.0,
-              got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
-            };
-
-            let value: String = list.next_into()?;
-            footprint.properties.insert(key, value);
-          }
+          "property" => footprint.properties.push(list.as_sexpr_into()?),
 
           name if name.starts_with("fp_") => footprint.graphics.push(list.as_sexpr_into()?),
 
+          _ if crate::parser::parse_options().collect_unknown => {
+            footprint.unknown.push(SExpr::List(list))
+          }
           name => crate::catch_all!(name),
         },
+        other if crate::parser::parse_options().collect_unknown => footprint.unknown.push(other),
         name => crate::catch_all!(name),
       }
     }
@@ -374,27 +1236,137 @@ impl TryFrom<SExpr> for Footprint {
   }
 }
 
+impl ToSExpr for Footprint {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("footprint")
+      .with_maybe(self.library_link.clone().map(SExpr::value))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with_maybe(self.placed.then(|| SExpr::symbol("placed")))
+      .with_maybe(self.version.as_ref().map(|version| {
+        let version = match version.parse::<i64>() {
+          Ok(version) => SExpr::Int(version),
+          Err(_) => SExpr::symbol(version),
+        };
+        SExpr::named("version", version)
+      }))
+      .with_maybe(
+        self
+          .generator
+          .clone()
+          .map(|generator| SExpr::named("generator", SExpr::value(generator))),
+      )
+      .with_maybe(
+        self
+          .generator_version
+          .clone()
+          .map(|version| SExpr::named("generator_version", SExpr::value(version))),
+      )
+      .with(self.layer.to_sexpr())
+      .with_maybe(
+        self
+          .tedit
+          .clone()
+          .map(|tedit| SExpr::named("tedit", SExpr::symbol(tedit))),
+      )
+      .with_maybe(self.uuid.as_ref().map(Uuid::to_sexpr))
+      .with_maybe(self.position.as_ref().map(Position::to_sexpr))
+      .with_maybe(
+        self
+          .description
+          .clone()
+          .map(|descr| SExpr::named("descr", SExpr::value(descr))),
+      )
+      .with_maybe(
+        self
+          .tags
+          .clone()
+          .map(|tags| SExpr::named("tags", SExpr::value(tags))),
+      )
+      .with_all(self.properties.iter().map(FootprintProperty::to_sexpr))
+      .with_maybe(
+        self
+          .path
+          .clone()
+          .map(|path| SExpr::named("path", SExpr::value(path))),
+      )
+      .with_maybe(
+        self
+          .autoplace_cost90
+          .map(|cost| SExpr::named("autoplace_cost90", cost)),
+      )
+      .with_maybe(
+        self
+          .autoplace_cost180
+          .map(|cost| SExpr::named("autoplace_cost180", cost)),
+      )
+      .with_maybe(
+        self
+          .solder_mask_margin
+          .map(|v| SExpr::named("solder_mask_margin", v)),
+      )
+      .with_maybe(
+        self
+          .solder_paste_margin
+          .map(|v| SExpr::named("solder_paste_margin", v)),
+      )
+      .with_maybe(
+        self
+          .solder_paste_ratio
+          .map(|v| SExpr::named("solder_paste_ratio", v)),
+      )
+      .with_maybe(self.clearance.map(|v| SExpr::named("clearance", v)))
+      .with_maybe(
+        self
+          .zone_connect
+          .clone()
+          .map(|v| SExpr::named("zone_connect", v as u8)),
+      )
+      .with_maybe(self.thermal_width.map(|v| SExpr::named("thermal_width", v)))
+      .with_maybe(self.thermal_gap.map(|v| SExpr::named("thermal_gap", v)))
+      .with_maybe(self.attributes.as_ref().map(FootprintAttributes::to_sexpr))
+      .with_maybe(
+        (!self.private_layers.is_empty())
+          .then(|| layer_list("private_layers", &self.private_layers)),
+      )
+      .with_maybe((!self.net_tie_pad_groups.is_empty()).then(|| {
+        SExprList::named("net_tie_pad_groups")
+          .with_all(
+            self
+              .net_tie_pad_groups
+              .iter()
+              .map(|group| SExpr::value(group.join(", "))),
+          )
+          .as_sexpr()
+      }))
+      .with_all(self.graphics.iter().map(Graphic::to_sexpr))
+      .with_all(self.pads.iter().map(Pad::to_sexpr))
+      .with_all(self.zones.iter().map(Zone::to_sexpr))
+      .with_all(self.groups.iter().map(Group::to_sexpr))
+      .with_maybe(
+        self
+          .embedded_fonts
+          .map(|embedded| SExpr::named("embedded_fonts", SExpr::yes_no(embedded))),
+      )
+      .with_maybe((!self.embedded_files.is_empty()).then(|| self.embedded_files.to_sexpr()))
+      .with_all(self.models.iter().map(Model3D::to_sexpr))
+      .with_all(self.unknown.iter().cloned())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for Footprint {
   fn bounding_box(&self) -> crate::common::BoundingBox {
-    let mut bounding = crate::common::BoundingBox::default();
-    for graphic in &self.graphics {
-      if graphic.layer() == "F.Fab" {
-        continue;
-      }
-
-      bounding.envelop(&graphic.bounding_box());
-    }
-
-    let position = self.position.as_ref();
-    let x = position.map_or(0.0, |p| p.x);
-    let y = position.map_or(0.0, |p| p.y);
-    bounding.move_by(x, y);
-    bounding
+    self
+      .graphics_in_board_space()
+      .iter()
+      .filter(|graphic| graphic.layer() != "F.Fab")
+      .map(Graphic::bounding_box)
+      .collect()
   }
 }
 
 /// Footprint pad
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Pad {
   /// Pad number
@@ -449,13 +1421,194 @@ pub struct Pad {
   pub thermal_width: Option<f64>,
   /// Thermal gap override
   pub thermal_gap: Option<f64>,
+  /// Thermal spoke angle override in degrees
+  pub thermal_bridge_angle: Option<f64>,
   /// Custom pad options
   pub custom_options: Option<CustomPadOptions>,
   /// Custom pad primitives
   pub custom_primitives: Vec<FootprintPolygon>,
+  /// Whether the shape was read from a shape token rather than left at its default. Not
+  /// compared nor serialized, since writing a pad always emits its shape.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub explicit_shape: bool,
+}
+
+/// Compares every field except [`Pad::explicit_shape`]. The destructuring makes a new field a
+/// compile error until it is compared here.
+impl PartialEq for Pad {
+  fn eq(&self, other: &Self) -> bool {
+    let Pad {
+      number,
+      pad_type,
+      shape,
+      position,
+      locked,
+      size,
+      drill,
+      layers,
+      properties,
+      remove_unused_layers,
+      keep_end_layers,
+      roundrect_rratio,
+      chamfer_ratio,
+      chamfer,
+      net,
+      uuid,
+      pin_function,
+      pin_type,
+      die_length,
+      solder_mask_margin,
+      solder_paste_margin,
+      solder_paste_margin_ratio,
+      clearance,
+      zone_connection,
+      thermal_width,
+      thermal_gap,
+      thermal_bridge_angle,
+      custom_options,
+      custom_primitives,
+      explicit_shape: _,
+    } = self;
+
+    *number == other.number
+      && *pad_type == other.pad_type
+      && *shape == other.shape
+      && *position == other.position
+      && *locked == other.locked
+      && *size == other.size
+      && *drill == other.drill
+      && *layers == other.layers
+      && *properties == other.properties
+      && *remove_unused_layers == other.remove_unused_layers
+      && *keep_end_layers == other.keep_end_layers
+      && *roundrect_rratio == other.roundrect_rratio
+      && *chamfer_ratio == other.chamfer_ratio
+      && *chamfer == other.chamfer
+      && *net == other.net
+      && *uuid == other.uuid
+      && *pin_function == other.pin_function
+      && *pin_type == other.pin_type
+      && *die_length == other.die_length
+      && *solder_mask_margin == other.solder_mask_margin
+      && *solder_paste_margin == other.solder_paste_margin
+      && *solder_paste_margin_ratio == other.solder_paste_margin_ratio
+      && *clearance == other.clearance
+      && *zone_connection == other.zone_connection
+      && *thermal_width == other.thermal_width
+      && *thermal_gap == other.thermal_gap
+      && *thermal_bridge_angle == other.thermal_bridge_angle
+      && *custom_options == other.custom_options
+      && *custom_primitives == other.custom_primitives
+  }
 }
 
 impl Pad {
+  /// Returns `false` when the pad was parsed without a recognized shape token, in which case
+  /// [`Pad::shape`] holds the default rather than what the file describes.
+  pub fn has_explicit_shape(&self) -> bool {
+    self.explicit_shape
+  }
+
+  fn set_shape(&mut self, shape: PadShape) {
+    self.shape = shape;
+    self.explicit_shape = true;
+  }
+
+  /// Returns the ordinal of the connected net, matching [`crate::pcb_file::PcbNet::ordinal`].
+  pub fn net_ordinal(&self) -> Option<i32> {
+    self.net.as_ref().map(|(ordinal, _)| *ordinal)
+  }
+
+  /// Returns the name of the connected net with KiCad's escape tokens replaced, see
+  /// [`crate::pcb_file::unescape_net_name`].
+  pub fn net_display_name(&self) -> Option<String> {
+    let (_, name) = self.net.as_ref()?;
+    Some(crate::pcb_file::unescape_net_name(name))
+  }
+
+  /// Area of the pad shape grown by `margin` on every side (shrunk when negative), in mm².
+  /// Custom pads are approximated by their anchor rectangle.
+  pub fn area_with_margin(&self, margin: f64) -> f64 {
+    self.area_with_margins(margin, margin)
+  }
+
+  /// Area of the pad shape grown by `margin_x` on the left and right and by `margin_y` on the top
+  /// and bottom, see [`Pad::area_with_margin`].
+  fn area_with_margins(&self, margin_x: f64, margin_y: f64) -> f64 {
+    let w = (self.size.0 + 2.0 * margin_x).max(0.0);
+    let h = (self.size.1 + 2.0 * margin_y).max(0.0);
+
+    match self.shape {
+      PadShape::Circle => std::f64::consts::PI * (w / 2.0).powi(2),
+      PadShape::Oval => {
+        let (long, short) = (w.max(h), w.min(h));
+        (long - short) * short + std::f64::consts::PI * (short / 2.0).powi(2)
+      }
+      PadShape::RoundedRectangle => {
+        // KiCad's default corner radius ratio
+        let radius = self.roundrect_rratio.unwrap_or(0.25) * w.min(h);
+        w * h - (4.0 - std::f64::consts::PI) * radius.powi(2)
+      }
+      PadShape::Rectangle | PadShape::Trapezoid | PadShape::Custom => w * h,
+    }
+  }
+
+  /// Width and height of the pad, circles using the first size as their diameter.
+  fn outline_size(&self) -> (f64, f64) {
+    match self.shape {
+      PadShape::Circle => (self.size.0, self.size.0),
+      _ => self.size,
+    }
+  }
+
+  /// Radius of the rounded corners, half the smaller side for circles and ovals.
+  fn corner_radius(&self) -> f64 {
+    let (w, h) = self.outline_size();
+    match self.shape {
+      PadShape::Circle | PadShape::Oval => w.min(h) / 2.0,
+      // KiCad's default corner radius ratio
+      PadShape::RoundedRectangle => self.roundrect_rratio.unwrap_or(0.25) * w.min(h),
+      PadShape::Rectangle | PadShape::Trapezoid | PadShape::Custom => 0.0,
+    }
+  }
+
+  /// Returns the outline of the pad as a polygon, placed and rotated by [`Pad::position`].
+  ///
+  /// Rounded corners are approximated with 8 segments per quarter turn. Trapezoids and custom
+  /// pads are outlined by their size rectangle, and chamfers are ignored.
+  pub fn outline_points(&self) -> Vec<Point> {
+    const SEGMENTS: usize = 8;
+    let (w, h) = self.outline_size();
+    let radius = self.corner_radius();
+    let (half_w, half_h) = (w / 2.0 - radius, h / 2.0 - radius);
+
+    let mut points = vec![];
+    for (quarter, (sx, sy)) in [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)]
+      .into_iter()
+      .enumerate()
+    {
+      // The end of a corner arc is also the start of the next one when no straight edge
+      // separates them, as on circles
+      let edge = if quarter % 2 == 0 { half_w } else { half_h };
+      let steps = match (radius > 0.0, edge > 0.0) {
+        (false, _) => 0,
+        (true, true) => SEGMENTS,
+        (true, false) => SEGMENTS - 1,
+      };
+
+      for step in 0..=steps {
+        let angle =
+          (quarter * SEGMENTS + step) as f64 * std::f64::consts::FRAC_PI_2 / SEGMENTS as f64;
+        points.push(self.position.transform_point((
+          sx * half_w + radius * angle.cos(),
+          sy * half_h + radius * angle.sin(),
+        )));
+      }
+    }
+
+    points
+  }
+
   pub fn translate(&self, position: &Position, mirror_local_x: bool) -> Self {
     let mut target = self.clone();
     if mirror_local_x {
@@ -467,18 +1620,10 @@ impl Pad {
 
   pub fn bounding_box_on_layer(&self, layer: &Layer) -> BoundingBox {
     let mut bbox = BoundingBox::default();
-    let angle = self.position.angle.unwrap_or(0.0);
     let flip = if layer.is_back() { -1.0 } else { 1.0 };
-    let anchor = Point {
-      x: self.position.x,
-      y: self.position.y,
-    };
 
     // Transforms a local pad coordinate to world (board) coordinate
-    let to_world = |lx, ly| {
-      let pt = Point::new(lx * flip, ly).rotate(-angle);
-      anchor + pt
-    };
+    let to_world = |lx: f64, ly: f64| self.position.transform_point((lx * flip, ly));
 
     match self.shape {
       // Simple shapes: add rotated rectangle corners
@@ -520,68 +1665,51 @@ impl GetBoundingBox for Pad {
     let mut bbox = BoundingBox::default();
 
     match self.shape {
-      // Simple shapes: bounding box = rectangle of size (w, h)
+      // Rounded shapes cover the rectangle between their corner centers, grown by the radius
       PadShape::Circle
       | PadShape::Rectangle
       | PadShape::Oval
       | PadShape::Trapezoid
       | PadShape::RoundedRectangle => {
-        let (w, h) = self.size;
-        let half_w = w / 2.0;
-        let half_h = h / 2.0;
+        let (w, h) = self.outline_size();
+        let radius = self.corner_radius();
+        let (half_w, half_h) = (w / 2.0 - radius, h / 2.0 - radius);
 
-        // Corners of the rectangle in local pad coordinates
         let corners = [
           (-half_w, -half_h),
           (half_w, -half_h),
           (half_w, half_h),
           (-half_w, half_h),
         ];
-
-        // Apply rotation and translation
-        let angle = self.position.angle; // in radians (or degrees, adapt rotate function)
-        for (local_x, local_y) in corners.iter() {
-          let rp = Point::new(*local_x, *local_y).rotate(-angle.unwrap_or(0.));
-          let px = self.position.x + rp.x;
-          let py = self.position.y + rp.y;
-          bbox.add_point(&Point { x: px, y: py });
-        }
+        bbox =
+          BoundingBox::from_points(corners.map(|corner| self.position.transform_point(corner)))
+            .expanded(radius);
       }
 
-      // Custom shape: process primitives
+      // Custom shape: process primitives, expanded by half their stroke width
       PadShape::Custom => {
-        let angle = self.position.angle;
-        let pad_anchor = Point::new(self.position.x, self.position.y);
-
         for prim in &self.custom_primitives {
+          let mut points = Vec::new();
           for item in &prim.points.0 {
             match item {
-              PointItem::Point(pt) => {
-                let rp = Point::new(pt.x, pt.y).rotate(-angle.unwrap_or(0.));
-                let mut pt = pad_anchor;
-                pt.x += rp.x;
-                pt.y += rp.y;
-                // Expand by half the stroke width to account for the outer edge
-                let half_stroke = prim.stroke.width / 2.0;
-                bbox.min_x = bbox.min_x.min(pt.x - half_stroke);
-                bbox.min_y = bbox.min_y.min(pt.y - half_stroke);
-                bbox.max_x = bbox.max_x.max(pt.x + half_stroke);
-                bbox.max_y = bbox.max_y.max(pt.y + half_stroke);
-
-                // Add point expanded by half stroke
-              }
+              PointItem::Point(point) => points.push(*point),
+              // The corners of the arc's box still contain the arc once rotated
               PointItem::Arc(arc) => {
-                let mut arc_bbox = arc.bounding_box_centerline();
-                let half_stroke = prim.stroke.width / 2.0;
-                arc_bbox.min_x -= half_stroke;
-                arc_bbox.min_y -= half_stroke;
-                arc_bbox.max_x += half_stroke;
-                arc_bbox.max_y += half_stroke;
-                bbox.envelop(&arc_bbox);
-                // Then rotate and translate each corner of arc_bbox (or envelop the bbox directly after rotation)
+                let arc_bbox = arc.bounding_box_centerline();
+                points.extend([
+                  Point::new(arc_bbox.min_x, arc_bbox.min_y),
+                  Point::new(arc_bbox.max_x, arc_bbox.min_y),
+                  Point::new(arc_bbox.max_x, arc_bbox.max_y),
+                  Point::new(arc_bbox.min_x, arc_bbox.max_y),
+                ]);
               }
             }
           }
+
+          let placed = points
+            .into_iter()
+            .map(|point| self.position.transform_point(point));
+          bbox.envelop(&BoundingBox::from_points(placed).expanded(prim.stroke.width / 2.0));
         }
       }
     }
@@ -605,19 +1733,19 @@ impl TryFrom<SExpr> for Pad {
     while let Some(list) = list.next_maybe() {
       match list {
         SExpr::Value(value) => pad.number = value.0,
-        SExpr::Symbol(s) if s == "locked" => pad.locked = false,
+        SExpr::Symbol(s) if s == "locked" => pad.locked = true,
 
         SExpr::Symbol(s) if s == "smd" => pad.pad_type = PadType::Smd,
         SExpr::Symbol(s) if s == "connect" => pad.pad_type = PadType::Connect,
         SExpr::Symbol(s) if s == "thru_hole" => pad.pad_type = PadType::ThroughHole,
         SExpr::Symbol(s) if s == "np_thru_hole" => pad.pad_type = PadType::NonPlatedThroughHole,
 
-        SExpr::Symbol(s) if s == "oval" => pad.shape = PadShape::Oval,
-        SExpr::Symbol(s) if s == "circle" => pad.shape = PadShape::Circle,
-        SExpr::Symbol(s) if s == "custom" => pad.shape = PadShape::Custom,
-        SExpr::Symbol(s) if s == "rect" => pad.shape = PadShape::Rectangle,
-        SExpr::Symbol(s) if s == "trapezoid" => pad.shape = PadShape::Trapezoid,
-        SExpr::Symbol(s) if s == "roundrect" => pad.shape = PadShape::RoundedRectangle,
+        SExpr::Symbol(s) if s == "oval" => pad.set_shape(PadShape::Oval),
+        SExpr::Symbol(s) if s == "circle" => pad.set_shape(PadShape::Circle),
+        SExpr::Symbol(s) if s == "custom" => pad.set_shape(PadShape::Custom),
+        SExpr::Symbol(s) if s == "rect" => pad.set_shape(PadShape::Rectangle),
+        SExpr::Symbol(s) if s == "trapezoid" => pad.set_shape(PadShape::Trapezoid),
+        SExpr::Symbol(s) if s == "roundrect" => pad.set_shape(PadShape::RoundedRectangle),
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "size" => {
@@ -630,6 +1758,19 @@ impl TryFrom<SExpr> for Pad {
           "at" => pad.position = attr.as_sexpr_into()?,
           "uuid" => pad.uuid = attr.as_sexpr_into()?,
           "layers" => pad.layers = attr.as_sexpr_into()?,
+          "drill" => pad.drill = Some(attr.as_sexpr_into()?),
+          "property" => match attr.discard(1)?.next_symbol()?.as_str() {
+            "pad_prop_heatsink" => pad.properties.push(PadProperty::Heatsink),
+            "pad_prop_castellated" => pad.properties.push(PadProperty::Castellated),
+            name => crate::catch_all!(name),
+          },
+          // Written without a value before version 8
+          "remove_unused_layers" => {
+            pad.remove_unused_layers = attr.0.len() == 1 || attr.read_yes_no()?
+          }
+          "keep_end_layers" => pad.keep_end_layers = attr.0.len() == 1 || attr.read_yes_no()?,
+          "options" => pad.custom_options = Some(attr.as_sexpr_into()?),
+          "roundrect_rratio" => pad.roundrect_rratio = Some(attr.read_named()?),
           "net" => {
             attr.discard(1)?; // Discard the "net" keyword
             let net_id: i32 = attr.next_into()?;
@@ -640,6 +1781,23 @@ impl TryFrom<SExpr> for Pad {
             attr.discard(1)?; // Discard the "pintype" keyword
             pad.pin_type = Some(attr.next_into()?);
           }
+          "pinfunction" => pad.pin_function = Some(attr.read_named()?),
+          "die_length" => pad.die_length = Some(attr.read_named()?),
+          "solder_mask_margin" => pad.solder_mask_margin = Some(attr.read_named()?),
+          "solder_paste_margin" => pad.solder_paste_margin = Some(attr.read_named()?),
+          "solder_paste_margin_ratio" => pad.solder_paste_margin_ratio = Some(attr.read_named()?),
+          "clearance" => pad.clearance = Some(attr.read_named()?),
+          "zone_connect" => pad.zone_connection = Some(attr.read_named()?),
+          "thermal_width" | "thermal_bridge_width" => pad.thermal_width = Some(attr.read_named()?),
+          "thermal_gap" => pad.thermal_gap = Some(attr.read_named()?),
+          "thermal_bridge_angle" => pad.thermal_bridge_angle = Some(attr.read_named()?),
+          "chamfer_ratio" => pad.chamfer_ratio = Some(attr.read_named()?),
+          "chamfer" => {
+            attr.discard(1)?; // Discard the "chamfer" keyword
+            while let Some(corner) = attr.next_maybe() {
+              pad.chamfer.push(corner.try_into()?);
+            }
+          }
           "primitives" => {
             attr.discard(1)?; // Discard the "pintype" keyword
             pad.custom_primitives = attr.try_into()?
@@ -650,10 +1808,153 @@ impl TryFrom<SExpr> for Pad {
       }
     }
 
+    if !pad.explicit_shape {
+      log::warn!(
+        "Pad {:?} has no shape token, assuming a rectangle",
+        pad.number
+      );
+    }
+
     Ok(pad)
   }
 }
 
+impl ToSExpr for Pad {
+  fn to_sexpr(&self) -> SExpr {
+    let pad_type = match self.pad_type {
+      PadType::ThroughHole => "thru_hole",
+      PadType::Smd => "smd",
+      PadType::Connect => "connect",
+      PadType::NonPlatedThroughHole => "np_thru_hole",
+    };
+    let shape = match self.shape {
+      PadShape::Circle => "circle",
+      PadShape::Rectangle => "rect",
+      PadShape::Oval => "oval",
+      PadShape::Trapezoid => "trapezoid",
+      PadShape::RoundedRectangle => "roundrect",
+      PadShape::Custom => "custom",
+    };
+
+    SExprList::named("pad")
+      .with(SExpr::value(&self.number))
+      .with(SExpr::symbol(pad_type))
+      .with(SExpr::symbol(shape))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.position.to_sexpr())
+      .with(
+        SExprList::named("size")
+          .with(self.size.0.into())
+          .with(self.size.1.into())
+          .as_sexpr(),
+      )
+      .with_maybe(self.drill.as_ref().map(Drill::to_sexpr))
+      .with_all(self.properties.iter().map(|property| {
+        SExpr::named(
+          "property",
+          SExpr::symbol(match property {
+            PadProperty::Heatsink => "pad_prop_heatsink",
+            PadProperty::Castellated => "pad_prop_castellated",
+          }),
+        )
+      }))
+      .with(self.layers.to_sexpr())
+      .with_maybe(
+        self
+          .remove_unused_layers
+          .then(|| SExpr::named("remove_unused_layers", SExpr::yes_no(true))),
+      )
+      .with_maybe(
+        self
+          .keep_end_layers
+          .then(|| SExpr::named("keep_end_layers", SExpr::yes_no(true))),
+      )
+      .with_maybe(
+        self
+          .roundrect_rratio
+          .map(|v| SExpr::named("roundrect_rratio", v)),
+      )
+      .with_maybe(self.chamfer_ratio.map(|v| SExpr::named("chamfer_ratio", v)))
+      .with_maybe((!self.chamfer.is_empty()).then(|| {
+        SExprList::named("chamfer")
+          .with_all(self.chamfer.iter().map(|corner| {
+            SExpr::symbol(match corner {
+              PadCorner::TopLeft => "top_left",
+              PadCorner::TopRight => "top_right",
+              PadCorner::BottomLeft => "bottom_left",
+              PadCorner::BottomRight => "bottom_right",
+            })
+          }))
+          .as_sexpr()
+      }))
+      .with_maybe(self.net.as_ref().map(|(id, name)| {
+        SExprList::named("net")
+          .with((*id).into())
+          .with(SExpr::value(name))
+          .as_sexpr()
+      }))
+      .with_maybe(
+        self
+          .pin_function
+          .clone()
+          .map(|function| SExpr::named("pinfunction", SExpr::value(function))),
+      )
+      .with_maybe(
+        self
+          .pin_type
+          .clone()
+          .map(|pin_type| SExpr::named("pintype", SExpr::value(pin_type))),
+      )
+      .with_maybe(self.die_length.map(|v| SExpr::named("die_length", v)))
+      .with_maybe(
+        self
+          .solder_mask_margin
+          .map(|v| SExpr::named("solder_mask_margin", v)),
+      )
+      .with_maybe(
+        self
+          .solder_paste_margin
+          .map(|v| SExpr::named("solder_paste_margin", v)),
+      )
+      .with_maybe(
+        self
+          .solder_paste_margin_ratio
+          .map(|v| SExpr::named("solder_paste_margin_ratio", v)),
+      )
+      .with_maybe(self.clearance.map(|v| SExpr::named("clearance", v)))
+      .with_maybe(
+        self
+          .zone_connection
+          .clone()
+          .map(|v| SExpr::named("zone_connect", v as u8)),
+      )
+      .with_maybe(
+        self
+          .thermal_width
+          .map(|v| SExpr::named("thermal_bridge_width", v)),
+      )
+      .with_maybe(self.thermal_gap.map(|v| SExpr::named("thermal_gap", v)))
+      .with_maybe(
+        self
+          .thermal_bridge_angle
+          .map(|v| SExpr::named("thermal_bridge_angle", v)),
+      )
+      .with_maybe(self.custom_options.as_ref().map(CustomPadOptions::to_sexpr))
+      .with_maybe((!self.custom_primitives.is_empty()).then(|| {
+        SExprList::named("primitives")
+          .with_all(
+            self
+              .custom_primitives
+              .iter()
+              .map(|primitive| primitive.to_prefixed_sexpr("gr")),
+          )
+          .as_sexpr()
+      }))
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 /// Pad types
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -698,6 +1999,21 @@ pub enum PadCorner {
   BottomRight,
 }
 
+impl TryFrom<SExpr> for PadCorner {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let corner: SExprSymbol = value.try_into()?;
+    match corner.0.as_str() {
+      "top_left" => Ok(PadCorner::TopLeft),
+      "top_right" => Ok(PadCorner::TopRight),
+      "bottom_left" => Ok(PadCorner::BottomLeft),
+      "bottom_right" => Ok(PadCorner::BottomRight),
+      found => crate::error!("Valid pad corner", found),
+    }
+  }
+}
+
 /// Drill definition
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -712,6 +2028,50 @@ pub struct Drill {
   pub offset: Option<Point>,
 }
 
+impl TryFrom<SExpr> for Drill {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "drill", "Drill::try_from");
+
+    let mut drill = Drill::default();
+    let mut diameter = None;
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(s) if s == "oval" => drill.oval = true,
+        // The first number is the diameter, oval drills follow it with the width
+        SExpr::Int(_) | SExpr::Float(_) | SExpr::Hex(_) if diameter.is_none() => {
+          diameter = Some(attr.try_into()?)
+        }
+        SExpr::Int(_) | SExpr::Float(_) | SExpr::Hex(_) if drill.oval && drill.width.is_none() => {
+          drill.width = Some(attr.try_into()?)
+        }
+
+        SExpr::List(attr) => match attr.peek_name()? {
+          "offset" => drill.offset = Some(attr.as_sexpr_into()?),
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+    drill.diameter = diameter.unwrap_or_default();
+
+    Ok(drill)
+  }
+}
+
+impl ToSExpr for Drill {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("drill")
+      .with_maybe(self.oval.then(|| SExpr::symbol("oval")))
+      .with(self.diameter.into())
+      .with_maybe(self.width.map(SExpr::from))
+      .with_maybe(self.offset.map(|offset| offset.to_named_sexpr("offset")))
+      .as_sexpr()
+  }
+}
+
 /// Custom pad options
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -722,6 +2082,59 @@ pub struct CustomPadOptions {
   pub anchor: PadShape,
 }
 
+impl TryFrom<SExpr> for CustomPadOptions {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "options", "CustomPadOptions::try_from");
+
+    let mut options = CustomPadOptions {
+      clearance: CustomPadClearance::Outline,
+      anchor: PadShape::Rectangle,
+    };
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "clearance" => {
+          options.clearance = match attr.discard(1)?.next_symbol()?.as_str() {
+            "outline" => CustomPadClearance::Outline,
+            "convexhull" => CustomPadClearance::ConvexHull,
+            found => crate::error!("outline | convexhull", found),
+          }
+        }
+        "anchor" => {
+          options.anchor = match attr.discard(1)?.next_symbol()?.as_str() {
+            "circle" => PadShape::Circle,
+            "rect" => PadShape::Rectangle,
+            found => crate::error!("circle | rect", found),
+          }
+        }
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(options)
+  }
+}
+
+impl ToSExpr for CustomPadOptions {
+  fn to_sexpr(&self) -> SExpr {
+    let clearance = match self.clearance {
+      CustomPadClearance::Outline => "outline",
+      CustomPadClearance::ConvexHull => "convexhull",
+    };
+    let anchor = match self.anchor {
+      PadShape::Circle => "circle",
+      _ => "rect",
+    };
+
+    SExprList::named("options")
+      .with(SExpr::named("clearance", SExpr::symbol(clearance)))
+      .with(SExpr::named("anchor", SExpr::symbol(anchor)))
+      .as_sexpr()
+  }
+}
+
 /// Custom pad clearance types
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -767,17 +2180,3 @@ pub enum PadGraphic {
     points: Vec<Point>,
   },
 }
-
-/// Zone definition (placeholder)
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct Zone {
-  // TODO: Implement zone structure
-}
-
-/// Group definition (placeholder)
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct Group {
-  // TODO: Implement group structure
-}
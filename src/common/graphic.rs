@@ -1,7 +1,10 @@
 use crate::{
-  common::{BoundingBox, GetBoundingBox, Layer, Point, PointItem, PointList, Position, Uuid},
+  common::{
+    Arc, Area, BoundingBox, GetBoundingBox, HorizontalJustify, Layer, Point, PointItem, PointList,
+    Position, TextEffects, Uuid, VerticalJustify,
+  },
   parser::ParserError,
-  sexpr::SExpr,
+  sexpr::{SExpr, SExprList, ToSExpr},
 };
 
 /// Stroke definition for drawing outlines
@@ -39,6 +42,25 @@ impl TryFrom<SExpr> for Stroke {
   }
 }
 
+impl ToSExpr for Stroke {
+  fn to_sexpr(&self) -> SExpr {
+    let line_type = match self.line_type {
+      StrokeType::Default => "default",
+      StrokeType::Solid => "solid",
+      StrokeType::Dash => "dash",
+      StrokeType::DashDot => "dash_dot",
+      StrokeType::DashDotDot => "dash_dot_dot",
+      StrokeType::Dot => "dot",
+    };
+
+    SExprList::named("stroke")
+      .with(SExpr::named("width", self.width))
+      .with(SExpr::named("type", SExpr::symbol(line_type)))
+      .with_maybe(self.color.as_ref().map(RgbaColor::to_sexpr))
+      .as_sexpr()
+  }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct RgbaColor(u8, u8, u8, u8);
@@ -59,6 +81,105 @@ impl TryFrom<SExpr> for RgbaColor {
   }
 }
 
+impl ToSExpr for RgbaColor {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("color")
+      .with(self.0.into())
+      .with(self.1.into())
+      .with(self.2.into())
+      .with(self.3.into())
+      .as_sexpr()
+  }
+}
+
+/// Fill style of a closed shape or zone
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FillType {
+  #[default]
+  None,
+  Solid,
+  /// Hatched fill, as used by zones with `(mode hatch)`, see [`Zone::fill`](crate::common::Zone::fill),
+  /// and shapes with a hatch fill
+  Hatch {
+    /// Gap between the hatch lines
+    spacing: Option<f64>,
+    /// Thickness of the hatch lines
+    thickness: Option<f64>,
+  },
+}
+
+impl TryFrom<SExpr> for FillType {
+  type Error = ParserError;
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "fill", "FillType::try_from");
+
+    let mut fill = Self::None;
+    let mut hatch = false;
+    let mut spacing = None;
+    let mut thickness = None;
+    while let Some(next) = list.next_maybe() {
+      match next {
+        SExpr::Symbol(s) => match s.as_str() {
+          "yes" | "solid" => fill = Self::Solid,
+          "no" | "none" => fill = Self::None,
+          "hatch" | "reverse_hatch" | "cross_hatch" => hatch = true,
+          name => crate::catch_all!(name),
+        },
+
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "mode" => hatch = attr.discard(1)?.next_symbol()? == "hatch",
+          "hatch_gap" => spacing = Some(attr.read_named()?),
+          "hatch_thickness" => thickness = Some(attr.read_named()?),
+          // Zone settings sharing the list, see `ZoneFillSettings`
+          "thermal_gap"
+          | "thermal_bridge_width"
+          | "smoothing"
+          | "radius"
+          | "island_removal_mode"
+          | "island_area_min"
+          | "hatch_orientation"
+          | "hatch_smoothing_level"
+          | "hatch_smoothing_value"
+          | "hatch_border_algorithm"
+          | "hatch_min_hole_area" => {}
+          name => crate::catch_all!(name),
+        },
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    if hatch {
+      fill = Self::Hatch { spacing, thickness };
+    }
+
+    Ok(fill)
+  }
+}
+
+impl FillType {
+  /// Whether the area is painted at all, either solid or hatched.
+  pub fn is_filled(&self) -> bool {
+    *self != FillType::None
+  }
+}
+
+impl ToSExpr for FillType {
+  fn to_sexpr(&self) -> SExpr {
+    match self {
+      FillType::None => SExpr::named("fill", SExpr::yes_no(false)),
+      FillType::Solid => SExpr::named("fill", SExpr::yes_no(true)),
+      FillType::Hatch { spacing, thickness } => SExprList::named("fill")
+        .with(SExpr::symbol("hatch"))
+        .with_maybe(spacing.map(|gap| SExpr::named("hatch_gap", gap)))
+        .with_maybe(thickness.map(|thickness| SExpr::named("hatch_thickness", thickness)))
+        .as_sexpr(),
+    }
+  }
+}
+
 /// Valid stroke line styles
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -85,7 +206,13 @@ impl TryFrom<SExpr> for StrokeType {
       "dot" => Self::Dot,
       "default" => Self::Default,
       "solid" => Self::Solid,
-      s => crate::error!("Valid stroke type", s),
+      // Styles from newer versions should not abort parsing the whole graphic
+      s => {
+        crate::parser::warn(|| {
+          ParserError::unexpected("stroke type", s).add_context(crate::context!())
+        });
+        Self::Default
+      }
     })
   }
 }
@@ -117,6 +244,139 @@ impl Graphic {
       Self::Curve(value) => &value.layer,
     }
   }
+
+  /// Converts the item back into an s-expression whose name starts with `prefix`: `fp` inside
+  /// footprints, `gr` on the board.
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    match self {
+      Self::Text(value) => value.to_prefixed_sexpr(prefix),
+      Self::TextBox(value) => value.to_prefixed_sexpr(prefix),
+      Self::Line(value) => value.to_prefixed_sexpr(prefix),
+      Self::Rectangle(value) => value.to_prefixed_sexpr(prefix),
+      Self::Circle(value) => value.to_prefixed_sexpr(prefix),
+      Self::Arc(value) => value.to_prefixed_sexpr(prefix),
+      Self::Polygon(value) => value.to_prefixed_sexpr(prefix),
+      Self::Curve(value) => value.to_prefixed_sexpr(prefix),
+    }
+  }
+
+  pub fn uuid(&self) -> &Uuid {
+    match self {
+      Self::Text(value) => &value.uuid,
+      Self::TextBox(value) => &value.uuid,
+      Self::Line(value) => &value.uuid,
+      Self::Rectangle(value) => &value.uuid,
+      Self::Circle(value) => &value.uuid,
+      Self::Arc(value) => &value.uuid,
+      Self::Polygon(value) => &value.uuid,
+      Self::Curve(value) => &value.uuid,
+    }
+  }
+
+  pub fn uuid_mut(&mut self) -> &mut Uuid {
+    match self {
+      Self::Text(value) => &mut value.uuid,
+      Self::TextBox(value) => &mut value.uuid,
+      Self::Line(value) => &mut value.uuid,
+      Self::Rectangle(value) => &mut value.uuid,
+      Self::Circle(value) => &mut value.uuid,
+      Self::Arc(value) => &mut value.uuid,
+      Self::Polygon(value) => &mut value.uuid,
+      Self::Curve(value) => &mut value.uuid,
+    }
+  }
+
+  /// Returns the drawn outline width: the stroke width, or the pre-version 7 `width` token when
+  /// the stroke has none. `None` for text, which has no outline.
+  pub fn effective_width(&self) -> Option<f64> {
+    match self {
+      Self::Text(_) | Self::TextBox(_) => None,
+      Self::Line(value) => Some(value.effective_width()),
+      Self::Rectangle(value) => Some(value.effective_width()),
+      Self::Circle(value) => Some(value.effective_width()),
+      Self::Arc(value) => Some(value.effective_width()),
+      Self::Polygon(value) => Some(value.effective_width()),
+      Self::Curve(value) => Some(value.effective_width()),
+    }
+  }
+
+  /// Whether the shape is filled, in which case it is visible without an outline.
+  pub fn is_filled(&self) -> bool {
+    match self {
+      Self::Rectangle(value) => value.fill.is_filled(),
+      Self::Circle(value) => value.fill.is_filled(),
+      Self::Polygon(value) => value.fill.is_filled(),
+      _ => false,
+    }
+  }
+  /// Maps a graphic of a footprint to board coordinates, `placement` being the footprint
+  /// position: points are rotated by its angle, then translated.
+  ///
+  /// Text angles are kept, as KiCad stores them including the footprint rotation. Rectangles
+  /// rotated by an angle that is not a multiple of 90° become polygons, since a rectangle is
+  /// always axis aligned.
+  pub fn transformed(&self, placement: &Position) -> Graphic {
+    let point = |point: &Point| placement.transform_point(*point);
+
+    match self {
+      Self::Text(text) => Self::Text(FootprintText {
+        position: placement.transform_position(&text.position),
+        ..text.clone()
+      }),
+      Self::TextBox(text) => Self::TextBox(FootprintTextBox {
+        start: text.start.as_ref().map(point),
+        end: text.end.as_ref().map(point),
+        points: text.points.transformed(placement),
+        ..text.clone()
+      }),
+      Self::Line(line) => Self::Line(FootprintLine {
+        start: point(&line.start),
+        end: point(&line.end),
+        ..line.clone()
+      }),
+      Self::Rectangle(rect) if placement.angle.unwrap_or(0.0) % 90.0 != 0.0 => {
+        let corners = [
+          rect.start,
+          Point::new(rect.end.x, rect.start.y),
+          rect.end,
+          Point::new(rect.start.x, rect.end.y),
+        ];
+        Self::Polygon(FootprintPolygon {
+          points: PointList(corners.iter().map(|c| PointItem::Point(point(c))).collect()),
+          layer: rect.layer.clone(),
+          stroke: rect.stroke.clone(),
+          fill: rect.fill.clone(),
+          locked: rect.locked,
+          uuid: rect.uuid.clone(),
+          width: f64::from(rect.width),
+        })
+      }
+      Self::Rectangle(rect) => Self::Rectangle(FootprintRectangle {
+        start: point(&rect.start),
+        end: point(&rect.end),
+        ..rect.clone()
+      }),
+      Self::Circle(circle) => Self::Circle(FootprintCircle {
+        center: point(&circle.center),
+        end: point(&circle.end),
+        ..circle.clone()
+      }),
+      Self::Arc(arc) => Self::Arc(FootprintArc {
+        start: point(&arc.start),
+        mid: point(&arc.mid),
+        end: point(&arc.end),
+        ..arc.clone()
+      }),
+      Self::Polygon(poly) => Self::Polygon(FootprintPolygon {
+        points: poly.points.transformed(placement),
+        ..poly.clone()
+      }),
+      Self::Curve(curve) => Self::Curve(FootprintCurve {
+        points: curve.points.transformed(placement),
+        ..curve.clone()
+      }),
+    }
+  }
 }
 
 impl GetBoundingBox for Graphic {
@@ -139,7 +399,8 @@ impl TryFrom<SExpr> for Graphic {
   fn try_from(value: SExpr) -> Result<Self, Self::Error> {
     let list = value.as_list()?;
     let full_name = list.peek_name()?;
-    let Some(name) = full_name.split("_").nth(1) else {
+    // Split off the `fp_` or `gr_` prefix only, so `gr_text_box` resolves to `text_box`
+    let Some((_, name)) = full_name.split_once('_') else {
       crate::error!("Valid footprint graphic type", full_name);
     };
 
@@ -158,18 +419,88 @@ impl TryFrom<SExpr> for Graphic {
   }
 }
 
-/// A macro that checks if the next symbol ends with a specific suffix.
-/// This is because graphics are marked by type `fp_<type>` for footprint graphics,
-///
+/// Implements [`ToSExpr`] for graphic items using the `fp` prefix of footprint graphics.
+macro_rules! footprint_graphic_to_sexpr {
+  ($($name:ty),* $(,)?) => {
+    $(
+      impl ToSExpr for $name {
+        fn to_sexpr(&self) -> SExpr {
+          self.to_prefixed_sexpr("fp")
+        }
+      }
+    )*
+  };
+}
+
+footprint_graphic_to_sexpr!(
+  Graphic,
+  FootprintText,
+  FootprintTextBox,
+  FootprintLine,
+  FootprintRectangle,
+  FootprintCircle,
+  FootprintArc,
+  FootprintPolygon,
+  FootprintCurve,
+);
+
+/// Implements `effective_width` for shapes, which store the stroke and the pre-version 7 `width`.
+macro_rules! shape_effective_width {
+  ($($name:ty),* $(,)?) => {
+    $(
+      impl $name {
+        /// Returns the drawn outline width: the stroke width, or the pre-version 7 `width` token
+        /// when the stroke has none.
+        pub fn effective_width(&self) -> f64 {
+          if self.stroke.width > 0.0 {
+            self.stroke.width
+          } else {
+            f64::from(self.width)
+          }
+        }
+      }
+    )*
+  };
+}
+
+shape_effective_width!(
+  FootprintLine,
+  FootprintRectangle,
+  FootprintCircle,
+  FootprintArc,
+  FootprintPolygon,
+  FootprintCurve,
+);
+
+/// Writes the stroke and the pre-version 7 width of a shape, each only when set.
+fn stroke_and_width(list: SExprList, stroke: &Stroke, width: impl Into<SExpr>) -> SExprList {
+  let width = width.into();
+  list
+    .with_maybe((*stroke != Stroke::default()).then(|| stroke.to_sexpr()))
+    .with_maybe((width != SExpr::Float(0.0)).then(|| SExpr::named("width", width)))
+}
+
+/// Checks that the next symbol names the graphic type ending in `$expected`, such as `_arc`.
+/// Graphics are named `fp_<type>` inside footprints and `gr_<type>` on the board, so both
+/// prefixes are accepted.
 macro_rules! symbol_ends_with {
   ($list: expr, $expected: literal) => {
     let found = $list.next_symbol()?.0;
-    if !found.ends_with($expected) {
-      return Err(ParserError::unexpected($expected, found));
+    let prefix = found.strip_suffix($expected);
+    if prefix != Some("fp") && prefix != Some("gr") {
+      return Err(ParserError::unexpected(
+        concat!("fp", $expected, " | gr", $expected),
+        found,
+      ));
     }
   };
 }
 
+/// Splits text content into lines.
+fn text_lines(text: &str) -> Vec<&str> {
+  text.split('\n').collect()
+}
+
 /// Footprint text
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -187,8 +518,7 @@ pub struct FootprintText {
   /// Hidden flag
   pub hide: bool,
   /// Text effects
-  /// TODO: Implement TextEffects
-  // pub effects: TextEffects,
+  pub effects: TextEffects,
   /// Unique identifier
   pub uuid: Uuid,
 }
@@ -205,18 +535,24 @@ impl TryFrom<SExpr> for FootprintText {
         SExpr::Value(value) => textbox.text = value.0,
 
         SExpr::Symbol(symbol) if symbol == "hide" => textbox.hide = true,
-        SExpr::Symbol(symbol) if symbol == "unlocked" => textbox.hide = true,
+        SExpr::Symbol(symbol) if symbol == "unlocked" => textbox.unlocked = true,
         SExpr::Symbol(symbol) if symbol == "reference" => {
           textbox.text_type = FootprintTextType::Reference
         }
         SExpr::Symbol(symbol) if symbol == "value" => textbox.text_type = FootprintTextType::Value,
         SExpr::Symbol(symbol) if symbol == "user" => textbox.text_type = FootprintTextType::User,
 
-        SExpr::List(attr) => match attr.peek_name()? {
+        SExpr::List(mut attr) => match attr.peek_name()? {
           "at" => textbox.position = attr.as_sexpr_into()?,
           "layer" => textbox.layer = attr.as_sexpr_into()?,
+          // Since version 8 the flags are written as `(unlocked yes)` and `(hide yes)`
+          "unlocked" => textbox.unlocked = attr.read_yes_no()?,
+          "hide" => textbox.hide = attr.read_yes_no()?,
           "uuid" => textbox.uuid = attr.as_sexpr_into()?,
-          // "effects" => ???
+          "effects" => {
+            textbox.effects = attr.as_sexpr_into()?;
+            textbox.hide |= textbox.effects.hide;
+          }
           other => crate::catch_all!(other),
         },
 
@@ -228,21 +564,72 @@ impl TryFrom<SExpr> for FootprintText {
   }
 }
 
+impl FootprintText {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    // Board text has no type token
+    let text_type = (prefix != "gr").then(|| {
+      SExpr::symbol(match self.text_type {
+        FootprintTextType::Reference => "reference",
+        FootprintTextType::Value => "value",
+        FootprintTextType::User => "user",
+      })
+    });
+
+    SExprList::named(&format!("{prefix}_text"))
+      .with_maybe(text_type)
+      .with(SExpr::value(&self.text))
+      .with(self.position.to_sexpr())
+      .with_maybe(self.unlocked.then(|| SExpr::symbol("unlocked")))
+      .with(self.layer.to_sexpr())
+      // Hidden text is written through the effects when they carry the flag
+      .with_maybe((self.hide && !self.effects.hide).then(|| SExpr::symbol("hide")))
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .with(self.effects.to_sexpr())
+      .as_sexpr()
+  }
+
+  /// Returns the lines of multi-line text content.
+  pub fn lines(&self) -> Vec<&str> {
+    text_lines(&self.text)
+  }
+}
+
 impl GetBoundingBox for FootprintText {
+  /// Approximates the extents of the text: every character is 0.6 times the font width wide and
+  /// every line one font height tall. The box is placed around the anchor following the
+  /// justification (centered by default) and rotated with the text.
   fn bounding_box(&self) -> BoundingBox {
-    let x = self.position.x;
-    let y = self.position.y;
-
-    // TODO: Calculate width and height based on text content
-    let width = 10.0; // Placeholder for text width
-    let height = 5.0; // Placeholder for text height
+    let lines = self.lines();
+    let longest = lines
+      .iter()
+      .map(|line| line.chars().count())
+      .max()
+      .unwrap_or(0);
+    let (font_height, font_width) = self.effects.font.size;
+    let width = longest as f64 * font_width * 0.6;
+    let height = lines.len() as f64 * font_height;
+
+    let justify = self.effects.justify.as_ref();
+    let left = match justify.and_then(|justify| justify.horizontal.as_ref()) {
+      Some(HorizontalJustify::Left) => 0.0,
+      Some(HorizontalJustify::Right) => -width,
+      Some(HorizontalJustify::Center) | None => -width / 2.0,
+    };
+    let top = match justify.and_then(|justify| justify.vertical.as_ref()) {
+      Some(VerticalJustify::Top) => 0.0,
+      Some(VerticalJustify::Bottom) => -height,
+      Some(VerticalJustify::Center) | None => -height / 2.0,
+    };
 
-    BoundingBox {
-      min_x: x,
-      min_y: y - height / 2.0,
-      max_x: x + width,
-      max_y: y + height / 2.0,
-    }
+    BoundingBox::from_points(
+      [
+        (left, top),
+        (left + width, top),
+        (left, top + height),
+        (left + width, top + height),
+      ]
+      .map(|corner| self.position.transform_point(corner)),
+    )
   }
 }
 
@@ -277,9 +664,11 @@ pub struct FootprintTextBox {
   /// Unique identifier
   pub uuid: Uuid,
   /// Text effects
-  // pub effects: TextEffects,
+  pub effects: TextEffects,
   /// Border stroke
   pub stroke: Option<Stroke>,
+  /// Whether the border is drawn, written since version 8
+  pub border: Option<bool>,
 }
 
 impl TryFrom<SExpr> for FootprintTextBox {
@@ -301,9 +690,10 @@ impl TryFrom<SExpr> for FootprintTextBox {
           "layer" => text.layer = attr.as_sexpr_into()?,
           "angle" => text.angle = attr.discard(1)?.next_maybe_into()?,
           "stroke" => text.stroke = Some(attr.as_sexpr_into()?),
+          "border" => text.border = Some(attr.read_yes_no()?),
           "pts" => text.points = attr.as_sexpr_into()?,
+          "effects" => text.effects = attr.as_sexpr_into()?,
 
-          // "effects" => ???
           other => crate::catch_all!(other),
         },
 
@@ -315,6 +705,33 @@ impl TryFrom<SExpr> for FootprintTextBox {
   }
 }
 
+impl FootprintTextBox {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    SExprList::named(&format!("{prefix}_text_box"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(SExpr::value(&self.text))
+      .with_maybe(self.start.map(|start| start.to_named_sexpr("start")))
+      .with_maybe(self.end.map(|end| end.to_named_sexpr("end")))
+      .with_maybe((!self.points.0.is_empty()).then(|| self.points.to_sexpr()))
+      .with_maybe(self.angle.map(|angle| SExpr::named("angle", angle)))
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .with(self.effects.to_sexpr())
+      .with_maybe(
+        self
+          .border
+          .map(|border| SExpr::named("border", SExpr::yes_no(border))),
+      )
+      .with_maybe(self.stroke.as_ref().map(Stroke::to_sexpr))
+      .as_sexpr()
+  }
+
+  /// Returns the lines of multi-line text content.
+  pub fn lines(&self) -> Vec<&str> {
+    text_lines(&self.text)
+  }
+}
+
 impl GetBoundingBox for FootprintTextBox {
   fn bounding_box(&self) -> BoundingBox {
     let min_x = self.start.as_ref().map(|f| f.x).unwrap_or_default();
@@ -360,7 +777,7 @@ impl TryFrom<SExpr> for FootprintLine {
     let mut line = Self::default();
     while let Some(attr) = list.next_maybe() {
       match attr {
-        SExpr::Symbol(s) if s == "locked" => line.locked = false,
+        SExpr::Symbol(s) if s == "locked" => line.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "start" => line.start = attr.as_sexpr_into()?,
@@ -382,13 +799,27 @@ impl TryFrom<SExpr> for FootprintLine {
   }
 }
 
+impl FootprintLine {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_line"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.start.to_named_sexpr("start"))
+      .with(self.end.to_named_sexpr("end"));
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for FootprintLine {
   fn bounding_box(&self) -> BoundingBox {
     BoundingBox {
-      min_x: self.start.x,
-      min_y: self.start.y,
-      max_x: self.end.x,
-      max_y: self.end.y,
+      min_x: self.start.x.min(self.end.x),
+      min_y: self.start.y.min(self.end.y),
+      max_x: self.start.x.max(self.end.x),
+      max_y: self.start.y.max(self.end.y),
     }
   }
 }
@@ -407,14 +838,20 @@ pub struct FootprintRectangle {
   pub layer: Layer,
   /// Stroke definition
   pub stroke: Stroke,
-  /// Fill flag
-  pub fill: bool,
+  /// Fill style
+  pub fill: FillType,
   /// Locked flag
   pub locked: bool,
   /// Unique identifier
   pub uuid: Uuid,
 }
 
+impl Area for FootprintRectangle {
+  fn area(&self) -> f64 {
+    ((self.end.x - self.start.x) * (self.end.y - self.start.y)).abs()
+  }
+}
+
 impl TryFrom<SExpr> for FootprintRectangle {
   type Error = ParserError;
   fn try_from(value: SExpr) -> Result<Self, Self::Error> {
@@ -424,7 +861,7 @@ impl TryFrom<SExpr> for FootprintRectangle {
     let mut rect = Self::default();
     while let Some(list) = list.next_maybe() {
       match list {
-        SExpr::Symbol(s) if s == "locked" => rect.locked = false,
+        SExpr::Symbol(s) if s == "locked" => rect.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "start" => rect.start = attr.as_sexpr_into()?,
@@ -433,7 +870,7 @@ impl TryFrom<SExpr> for FootprintRectangle {
           "layer" => rect.layer = attr.as_sexpr_into()?,
           "stroke" => rect.stroke = attr.as_sexpr_into()?,
           "uuid" => rect.uuid = attr.as_sexpr_into()?,
-          "fill" => rect.fill = attr.discard(1)?.next_symbol()? == "yes",
+          "fill" => rect.fill = attr.as_sexpr_into()?,
           "width" => rect.width = attr.discard(1)?.next_into()?,
 
           name => crate::catch_all!(name),
@@ -446,13 +883,28 @@ impl TryFrom<SExpr> for FootprintRectangle {
   }
 }
 
+impl FootprintRectangle {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_rect"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.start.to_named_sexpr("start"))
+      .with(self.end.to_named_sexpr("end"));
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.fill.to_sexpr())
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for FootprintRectangle {
   fn bounding_box(&self) -> BoundingBox {
     BoundingBox {
-      min_x: self.start.x,
-      min_y: self.start.y,
-      max_x: self.end.x,
-      max_y: self.end.y,
+      min_x: self.start.x.min(self.end.x),
+      min_y: self.start.y.min(self.end.y),
+      max_x: self.start.x.max(self.end.x),
+      max_y: self.start.y.max(self.end.y),
     }
   }
 }
@@ -471,14 +923,36 @@ pub struct FootprintCircle {
   pub stroke: Stroke,
   // The width token defines the line width of the circle. (prior to version 7)
   pub width: f32,
-  /// Fill flag
-  pub fill: bool,
+  /// Fill style
+  pub fill: FillType,
   /// Locked flag
   pub locked: bool,
   /// Unique identifier
   pub uuid: Uuid,
 }
 
+impl Area for FootprintCircle {
+  fn area(&self) -> f64 {
+    let radius = (self.end.x - self.center.x).hypot(self.end.y - self.center.y);
+    std::f64::consts::PI * radius * radius
+  }
+}
+
+impl FootprintCircle {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_circle"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.center.to_named_sexpr("center"))
+      .with(self.end.to_named_sexpr("end"));
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.fill.to_sexpr())
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for FootprintCircle {
   fn bounding_box(&self) -> BoundingBox {
     let radius =
@@ -502,7 +976,7 @@ impl TryFrom<SExpr> for FootprintCircle {
     let mut circle = Self::default();
     while let Some(list) = list.next_maybe() {
       match list {
-        SExpr::Symbol(s) if s == "locked" => circle.locked = false,
+        SExpr::Symbol(s) if s == "locked" => circle.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "center" => circle.center = attr.as_sexpr_into()?,
@@ -511,7 +985,7 @@ impl TryFrom<SExpr> for FootprintCircle {
           "layer" => circle.layer = attr.as_sexpr_into()?,
           "stroke" => circle.stroke = attr.as_sexpr_into()?,
           "uuid" => circle.uuid = attr.as_sexpr_into()?,
-          "fill" => circle.fill = attr.discard(1)?.next_symbol()? == "yes",
+          "fill" => circle.fill = attr.as_sexpr_into()?,
           "width" => circle.width = attr.discard(1)?.next_into()?,
 
           name => crate::catch_all!(name),
@@ -555,7 +1029,7 @@ impl TryFrom<SExpr> for FootprintArc {
     let mut arc = Self::default();
     while let Some(list) = list.next_maybe() {
       match list {
-        SExpr::Symbol(s) if s == "locked" => arc.locked = false,
+        SExpr::Symbol(s) if s == "locked" => arc.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "start" => arc.start = attr.as_sexpr_into()?,
@@ -577,19 +1051,30 @@ impl TryFrom<SExpr> for FootprintArc {
   }
 }
 
+impl FootprintArc {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_arc"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.start.to_named_sexpr("start"))
+      .with(self.mid.to_named_sexpr("mid"))
+      .with(self.end.to_named_sexpr("end"));
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for FootprintArc {
+  /// Bounds the swept arc, including the points where it crosses the circle's extremes.
   fn bounding_box(&self) -> BoundingBox {
-    let min_x = self.start.x.min(self.end.x).min(self.mid.x);
-    let min_y = self.start.y.min(self.end.y).min(self.mid.y);
-    let max_x = self.start.x.max(self.end.x).max(self.mid.x);
-    let max_y = self.start.y.max(self.end.y).max(self.mid.y);
-
-    BoundingBox {
-      min_x,
-      min_y,
-      max_x,
-      max_y,
+    Arc {
+      start: self.start,
+      mid: self.mid,
+      end: self.end,
     }
+    .bounding_box_centerline()
   }
 }
 
@@ -603,8 +1088,8 @@ pub struct FootprintPolygon {
   pub layer: Layer,
   /// Stroke definition
   pub stroke: Stroke,
-  /// Fill flag
-  pub fill: bool,
+  /// Fill style
+  pub fill: FillType,
   /// Locked flag
   pub locked: bool,
   /// Unique identifier
@@ -622,7 +1107,7 @@ impl TryFrom<SExpr> for FootprintPolygon {
     let mut poly = Self::default();
     while let Some(list) = list.next_maybe() {
       match list {
-        SExpr::Symbol(s) if s == "locked" => poly.locked = false,
+        SExpr::Symbol(s) if s == "locked" => poly.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "pts" => poly.points = attr.as_sexpr_into()?,
@@ -631,7 +1116,7 @@ impl TryFrom<SExpr> for FootprintPolygon {
           "stroke" => poly.stroke = attr.as_sexpr_into()?,
           "uuid" => poly.uuid = attr.as_sexpr_into()?,
           "width" => poly.width = attr.discard(1)?.next_into()?,
-          "fill" => poly.fill = attr.discard(1)?.next_symbol()? == "yes",
+          "fill" => poly.fill = attr.as_sexpr_into()?,
 
           name => crate::catch_all!(name),
         },
@@ -643,16 +1128,51 @@ impl TryFrom<SExpr> for FootprintPolygon {
   }
 }
 
-impl GetBoundingBox for FootprintPolygon {
-  fn bounding_box(&self) -> BoundingBox {
-    let mut bbox = BoundingBox::default();
+impl FootprintPolygon {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_poly"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.points.to_sexpr());
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.fill.to_sexpr())
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
+impl Area for FootprintPolygon {
+  /// Uses the shoelace formula, with arc segments flattened into short chords first.
+  fn area(&self) -> f64 {
+    let mut outline = Vec::new();
     for item in &self.points.0 {
       match item {
-        PointItem::Point(p) => bbox.add_point(p),
-        PointItem::Arc(arc) => bbox.envelop(&arc.bounding_box_centerline()),
+        PointItem::Point(p) => outline.push(*p),
+        PointItem::Arc(arc) => outline.extend(arc.sample_points(64)),
       }
     }
-    bbox
+
+    let twice_area: f64 = outline
+      .iter()
+      .zip(outline.iter().cycle().skip(1))
+      .map(|(a, b)| a.x * b.y - b.x * a.y)
+      .sum();
+    twice_area.abs() / 2.0
+  }
+}
+
+impl GetBoundingBox for FootprintPolygon {
+  fn bounding_box(&self) -> BoundingBox {
+    self
+      .points
+      .0
+      .iter()
+      .map(|item| match item {
+        PointItem::Point(p) => BoundingBox::from_points([*p]),
+        PointItem::Arc(arc) => arc.bounding_box_centerline(),
+      })
+      .collect()
   }
 }
 
@@ -683,7 +1203,7 @@ impl TryFrom<SExpr> for FootprintCurve {
     let mut curve = Self::default();
     while let Some(list) = list.next_maybe() {
       match list {
-        SExpr::Symbol(s) if s == "locked" => curve.locked = false,
+        SExpr::Symbol(s) if s == "locked" => curve.locked = true,
 
         SExpr::List(mut attr) => match attr.peek_name()? {
           "pts" => curve.points = attr.as_sexpr_into()?,
@@ -703,8 +1223,83 @@ impl TryFrom<SExpr> for FootprintCurve {
   }
 }
 
+impl FootprintCurve {
+  pub fn to_prefixed_sexpr(&self, prefix: &str) -> SExpr {
+    let list = SExprList::named(&format!("{prefix}_curve"))
+      .with_maybe(self.locked.then(|| SExpr::symbol("locked")))
+      .with(self.points.to_sexpr());
+
+    stroke_and_width(list, &self.stroke, self.width)
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
 impl GetBoundingBox for FootprintCurve {
+  /// Bounds a cubic Bezier curve by its start, end and axis extremes. Point lists other than four
+  /// plain control points fall back to the bounds of the points and arcs, which always contain the
+  /// curve.
   fn bounding_box(&self) -> BoundingBox {
-    todo!("Bazier curves not implemented yet")
+    let controls: Vec<Point> = self
+      .points
+      .0
+      .iter()
+      .filter_map(|item| match item {
+        PointItem::Point(p) => Some(*p),
+        PointItem::Arc(_) => None,
+      })
+      .collect();
+
+    match (controls.as_slice(), self.points.0.len()) {
+      ([p0, p1, p2, p3], 4) => {
+        let mut ts = cubic_extremes(p0.x, p1.x, p2.x, p3.x);
+        ts.extend(cubic_extremes(p0.y, p1.y, p2.y, p3.y));
+        ts.extend([0.0, 1.0]);
+
+        BoundingBox::from_points(ts.into_iter().map(|t| {
+          let u = 1.0 - t;
+          let at = |a: f64, b: f64, c: f64, d: f64| {
+            u * u * u * a + 3.0 * u * u * t * b + 3.0 * u * t * t * c + t * t * t * d
+          };
+          Point::new(at(p0.x, p1.x, p2.x, p3.x), at(p0.y, p1.y, p2.y, p3.y))
+        }))
+      }
+      _ => self
+        .points
+        .0
+        .iter()
+        .map(|item| match item {
+          PointItem::Point(p) => BoundingBox::from_points([*p]),
+          PointItem::Arc(arc) => arc.bounding_box_centerline(),
+        })
+        .collect(),
+    }
   }
 }
+
+/// Returns the parameters in (0, 1) where one axis of a cubic Bezier curve with the given control
+/// coordinates has a zero derivative.
+fn cubic_extremes(p0: f64, p1: f64, p2: f64, p3: f64) -> Vec<f64> {
+  let a = 3.0 * (-p0 + 3.0 * p1 - 3.0 * p2 + p3);
+  let b = 6.0 * (p0 - 2.0 * p1 + p2);
+  let c = 3.0 * (p1 - p0);
+
+  let roots = if a.abs() < 1e-12 {
+    if b.abs() < 1e-12 {
+      vec![]
+    } else {
+      vec![-c / b]
+    }
+  } else {
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+      vec![]
+    } else {
+      let sqrt = discriminant.sqrt();
+      vec![(-b + sqrt) / (2.0 * a), (-b - sqrt) / (2.0 * a)]
+    }
+  };
+
+  roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
@@ -0,0 +1,63 @@
+use crate::{
+  common::Uuid,
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Named group of board items, which KiCad selects and moves together
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Group {
+  /// Group name, empty for unnamed groups
+  pub name: String,
+  /// Unique identifier
+  pub uuid: Uuid,
+  /// Identifiers of the grouped items
+  pub members: Vec<Uuid>,
+}
+
+impl TryFrom<SExpr> for Group {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "group", "Group::try_from");
+
+    let mut group = Group {
+      name: list.next_into()?,
+      ..Default::default()
+    };
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "uuid" => group.uuid = attr.as_sexpr_into()?,
+        // Files prior to version 8 name the identifier `id`
+        "id" => group.uuid = Uuid(attr.read_named()?),
+        "members" => {
+          attr.discard(1)?; // Discard the "members" keyword
+          while let Some(member) = attr.next_maybe_into::<String>()? {
+            group.members.push(Uuid(member));
+          }
+        }
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(group)
+  }
+}
+
+impl ToSExpr for Group {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("group")
+      .with(SExpr::value(&self.name))
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .with(
+        SExprList::named("members")
+          .with_all(self.members.iter().map(|member| SExpr::value(&member.0)))
+          .as_sexpr(),
+      )
+      .as_sexpr()
+  }
+}
@@ -1,8 +1,29 @@
+mod canonical_layer;
+pub use canonical_layer::*;
+
+mod dimension;
+pub use dimension::*;
+
+mod embedded_file;
+pub use embedded_file::*;
+
 mod graphic;
 pub use graphic::*;
 
 mod footprint;
 pub use footprint::*;
 
+mod group;
+pub use group::*;
+
+mod paper;
+pub use paper::*;
+
 mod positionals;
 pub use positionals::*;
+
+mod title_block;
+pub use title_block::*;
+
+mod zone;
+pub use zone::*;
@@ -0,0 +1,100 @@
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Sheet size of the drawing, e.g. `(paper "A4" portrait)` or `(paper "User" 297 210)`
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Paper {
+  /// Named size such as `A4` or `USLetter`, `User` for a custom size
+  pub size: String,
+  /// Width and height in millimeters of a `User` size
+  pub custom_size: Option<(f64, f64)>,
+  /// Portrait orientation, landscape otherwise
+  pub portrait: bool,
+}
+
+/// Landscape dimensions in millimeters of the named sizes KiCad knows.
+const PAPER_SIZES: &[(&str, f64, f64)] = &[
+  ("A5", 210.0, 148.0),
+  ("A4", 297.0, 210.0),
+  ("A3", 420.0, 297.0),
+  ("A2", 594.0, 420.0),
+  ("A1", 841.0, 594.0),
+  ("A0", 1189.0, 841.0),
+  ("A", 279.4, 215.9),
+  ("B", 431.8, 279.4),
+  ("C", 558.8, 431.8),
+  ("D", 863.6, 558.8),
+  ("E", 1117.6, 863.6),
+  ("USLetter", 279.4, 215.9),
+  ("USLegal", 355.6, 215.9),
+  ("USLedger", 431.8, 279.4),
+];
+
+impl Paper {
+  /// Returns the sheet width and height in millimeters, taking the orientation into account.
+  ///
+  /// Custom sizes are returned as written. Returns `None` for unknown names.
+  pub fn dimensions(&self) -> Option<(f64, f64)> {
+    if let Some(size) = self.custom_size {
+      return Some(size);
+    }
+
+    let &(_, width, height) = PAPER_SIZES.iter().find(|(name, ..)| *name == self.size)?;
+    Some(if self.portrait {
+      (height, width)
+    } else {
+      (width, height)
+    })
+  }
+}
+
+impl TryFrom<SExpr> for Paper {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "paper", "Paper::try_from");
+
+    // KiCad 5 leaves the name unquoted
+    let size = match list.next_any()? {
+      SExpr::Value(value) => value.0,
+      SExpr::Symbol(symbol) => symbol.0,
+      got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+    };
+
+    let mut paper = Paper {
+      size,
+      ..Default::default()
+    };
+    if paper.size == "User" {
+      paper.custom_size = Some((list.next_into()?, list.next_into()?));
+    }
+
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(symbol) if symbol == "portrait" => paper.portrait = true,
+        other => crate::catch_all!(other),
+      }
+    }
+
+    Ok(paper)
+  }
+}
+
+impl ToSExpr for Paper {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("paper")
+      .with(SExpr::value(&self.size))
+      .with_all(
+        self
+          .custom_size
+          .into_iter()
+          .flat_map(|(width, height)| [width.into(), height.into()]),
+      )
+      .with_maybe(self.portrait.then(|| SExpr::symbol("portrait")))
+      .as_sexpr()
+  }
+}
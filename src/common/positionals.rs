@@ -3,17 +3,23 @@ use std::{
   ops::{Add, AddAssign, Div},
 };
 
-use crate::{parser::ParserError, sexpr::SExpr};
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
 
 /// Position identifier defining X/Y coordinates and optional rotation angle
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Position {
   /// X coordinate in millimeters
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_rounded"))]
   pub x: f64,
   /// Y coordinate in millimeters  
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_rounded"))]
   pub y: f64,
   /// Optional rotation angle in degrees
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_rounded_maybe"))]
   pub angle: Option<f64>,
 }
 
@@ -26,6 +32,13 @@ impl Position {
     }
   }
 
+  /// Returns the coordinates of the position in integer nanometers, see [`PointFixed`].
+  pub fn to_fixed(&self) -> PointFixed {
+    Point::new(self.x, self.y).to_fixed()
+  }
+
+  /// Converts a position relative to this one, such as a pad inside a footprint, into an absolute
+  /// position by rotating it by this angle and translating it. The angle of `sub_pos` is kept.
   pub fn transform_position(&self, sub_pos: &Position) -> Position {
     if let Some(angle) = self.angle {
       let angle = angle.to_radians();
@@ -43,13 +56,15 @@ impl Position {
     }
   }
 
+  /// Rotates a vector such as a pad size by the angle of this position, without translating it.
+  /// The rotation matches [`Position::transform_position`].
   pub fn transform_angle(&self, point: impl Into<Point>) -> Point {
     let point = point.into();
     if let Some(angle) = self.angle {
       let angle = angle.to_radians();
       Point {
         x: point.x * angle.cos() + point.y * angle.sin(),
-        y: point.x * angle.sin() - point.y * angle.cos(),
+        y: -point.x * angle.sin() + point.y * angle.cos(),
       }
     } else {
       point
@@ -79,8 +94,8 @@ impl TryFrom<SExpr> for Position {
     let mut list = value.as_list()?;
     crate::expect_eq!(list.next_symbol()?, "at", "Position::try_from");
 
-    let x: f64 = list.next_into()?;
-    let y: f64 = list.next_into()?;
+    let x = parsed_coordinate(list.next_into()?);
+    let y = parsed_coordinate(list.next_into()?);
     let angle: Option<f64> = list.next_maybe_into()?;
     list.expect_end()?;
 
@@ -88,6 +103,16 @@ impl TryFrom<SExpr> for Position {
   }
 }
 
+impl ToSExpr for Position {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("at")
+      .with(self.x.into())
+      .with(self.y.into())
+      .with_maybe(self.angle.map(SExpr::from))
+      .as_sexpr()
+  }
+}
+
 impl From<(f64, f64)> for Point {
   fn from(value: (f64, f64)) -> Self {
     Point {
@@ -102,16 +127,83 @@ impl From<(f64, f64)> for Point {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Point {
   /// X coordinate in millimeters
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_rounded"))]
   pub x: f64,
   /// Y coordinate in millimeters
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_rounded"))]
   pub y: f64,
 }
 
-fn rotate_point(x: f64, y: f64, angle: f64) -> (f64, f64) {
-  let angle = angle.to_radians();
-  let cos_a = angle.cos();
-  let sin_a = angle.sin();
-  (x * cos_a + y * sin_a, x * sin_a - y * cos_a)
+/// Number of nanometers in a millimeter, KiCad's internal resolution.
+pub const NM_PER_MM: f64 = 1_000_000.0;
+
+/// Converts millimeters to integer nanometers, rounding to the nearest nanometer.
+pub fn mm_to_nm(mm: f64) -> i64 {
+  (mm * NM_PER_MM).round() as i64
+}
+
+/// Converts integer nanometers back to millimeters.
+pub fn nm_to_mm(nm: i64) -> f64 {
+  nm as f64 / NM_PER_MM
+}
+
+/// Rounds a parsed coordinate to whole nanometers when
+/// [`fixed_point`](crate::parser::ParseOptions::fixed_point) parsing is on.
+fn parsed_coordinate(mm: f64) -> f64 {
+  if crate::parser::parse_options().fixed_point {
+    nm_to_mm(mm_to_nm(mm))
+  } else {
+    mm
+  }
+}
+
+/// Serializes a value rounded to 6 decimals, the precision KiCad stores, to keep float noise out
+/// of the output.
+#[cfg(feature = "serde")]
+fn serialize_rounded<S: serde::Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.serialize_f64(nm_to_mm(mm_to_nm(*value)))
+}
+
+#[cfg(feature = "serde")]
+fn serialize_rounded_maybe<S: serde::Serializer>(
+  value: &Option<f64>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  match value {
+    Some(value) => serializer.serialize_some(&nm_to_mm(mm_to_nm(*value))),
+    None => serializer.serialize_none(),
+  }
+}
+
+/// Coordinate point in integer nanometers, for comparing coordinates exactly without float drift
+///
+/// Parse with [`fixed_point`](crate::parser::ParseOptions::fixed_point) set to round the parsed
+/// coordinates themselves to the same grid.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PointFixed {
+  /// X coordinate in nanometers
+  pub x: i64,
+  /// Y coordinate in nanometers
+  pub y: i64,
+}
+
+impl PointFixed {
+  pub fn to_point(&self) -> Point {
+    Point::new(nm_to_mm(self.x), nm_to_mm(self.y))
+  }
+}
+
+impl From<Point> for PointFixed {
+  fn from(value: Point) -> Self {
+    value.to_fixed()
+  }
+}
+
+impl From<PointFixed> for Point {
+  fn from(value: PointFixed) -> Self {
+    value.to_point()
+  }
 }
 
 impl Point {
@@ -119,15 +211,36 @@ impl Point {
     (self.x, self.y)
   }
 
-  /// Returns a new `Point` rotated counter-clockwise around the origin by `angle` (radians).
+  /// Returns the point in integer nanometers, see [`PointFixed`].
+  pub fn to_fixed(&self) -> PointFixed {
+    PointFixed {
+      x: mm_to_nm(self.x),
+      y: mm_to_nm(self.y),
+    }
+  }
+
+  /// Returns a new `Point` rotated around the origin by `angle` degrees, counter-clockwise as
+  /// KiCad displays it. The rotation matches [`Position::transform_angle`].
   pub fn rotate(&self, angle: f64) -> Point {
-    let (rx, ry) = rotate_point(self.x, self.y, angle);
-    Point { x: rx, y: ry }
+    Position {
+      x: 0.,
+      y: 0.,
+      angle: Some(angle),
+    }
+    .transform_angle(*self)
   }
 
   pub fn new(x: f64, y: f64) -> Self {
     Point { x, y }
   }
+
+  /// Serializes the point as a named coordinate list such as `(start x y)`.
+  pub fn to_named_sexpr(&self, name: &str) -> SExpr {
+    SExprList::named(name)
+      .with(SExpr::Float(self.x))
+      .with(SExpr::Float(self.y))
+      .as_sexpr()
+  }
 }
 
 impl Add for Point {
@@ -160,18 +273,21 @@ impl TryFrom<SExpr> for Point {
     let mut list = value.as_list()?;
     let _name = list.next_symbol()?;
 
-    let x: f64 = list.next_into()?;
-    let y: f64 = list.next_into()?;
+    let x = parsed_coordinate(list.next_into()?);
+    let y = parsed_coordinate(list.next_into()?);
     list.expect_end()?;
 
     Ok(Point { x, y })
   }
 }
 
+/// An entry of a point list: either a plain vertex or an arc segment.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PointItem {
+  /// A vertex written as `(xy x y)`
   Point(Point),
+  /// An arc segment written as `(arc (start x y) (mid x y) (end x y))`
   Arc(Arc),
 }
 
@@ -187,6 +303,11 @@ impl Arc {
   /// Returns the axis-aligned bounding box of the arc's centerline (no stroke width).
   pub fn bounding_box_centerline(&self) -> BoundingBox {
     let (cx, cy, r) = circle_from_three_points(&self.start, &self.mid, &self.end);
+    if r == 0.0 {
+      // Collinear points describe a straight segment
+      return BoundingBox::from_points([self.start, self.mid, self.end]);
+    }
+
     let start_angle = normalize_angle((self.start.y - cy).atan2(self.start.x - cx));
     let mid_angle = normalize_angle((self.mid.y - cy).atan2(self.mid.x - cx));
     let end_angle = normalize_angle((self.end.y - cy).atan2(self.end.x - cx));
@@ -215,16 +336,21 @@ impl Arc {
     bbox
   }
 
-  /// Generates `num_points` evenly spaced points along the arc.
+  /// Generates `num_points` evenly spaced points along the arc, running from `start` to `end`.
   pub fn sample_points(&self, num_points: usize) -> Vec<Point> {
     let (cx, cy, r) = circle_from_three_points(&self.start, &self.mid, &self.end);
+    if r == 0.0 {
+      // Collinear points describe a straight segment
+      return vec![self.start, self.end];
+    }
+
     let start_angle = normalize_angle((self.start.y - cy).atan2(self.start.x - cx));
     let mid_angle = normalize_angle((self.mid.y - cy).atan2(self.mid.x - cx));
     let end_angle = normalize_angle((self.end.y - cy).atan2(self.end.x - cx));
     let (angle_start, angle_end) = get_arc_interval(start_angle, end_angle, mid_angle);
 
     let n = num_points.max(2);
-    (0..n)
+    let mut points: Vec<Point> = (0..n)
       .map(|i| {
         let t = angle_start + (i as f64) * (angle_end - angle_start) / ((n - 1) as f64);
         Point {
@@ -232,7 +358,23 @@ impl Arc {
           y: cy + r * t.sin(),
         }
       })
-      .collect()
+      .collect();
+
+    // The interval always runs counter-clockwise, which goes from the end for clockwise arcs
+    if (angle_start - start_angle).abs() > 1e-12 {
+      points.reverse();
+    }
+    points
+  }
+}
+
+impl ToSExpr for Arc {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("arc")
+      .with(self.start.to_named_sexpr("start"))
+      .with(self.mid.to_named_sexpr("mid"))
+      .with(self.end.to_named_sexpr("end"))
+      .as_sexpr()
   }
 }
 
@@ -344,9 +486,8 @@ impl TryFrom<SExpr> for PointList {
         }
         "arc" => {
           pt.next_symbol()?; // arc
-          let mut list = pt.next_list()?;
           let mut arc = Arc::default();
-          while let Some(list) = list.next_maybe() {
+          while let Some(list) = pt.next_maybe() {
             match list {
               SExpr::List(attr) => match attr.peek_name()? {
                 "start" => arc.start = attr.as_sexpr_into()?,
@@ -368,6 +509,40 @@ impl TryFrom<SExpr> for PointList {
   }
 }
 
+impl PointList {
+  /// Returns the points mapped through [`Position::transform_point`].
+  pub fn transformed(&self, placement: &Position) -> PointList {
+    let point = |point: &Point| placement.transform_point(*point);
+    PointList(
+      self
+        .0
+        .iter()
+        .map(|item| match item {
+          PointItem::Point(p) => PointItem::Point(point(p)),
+          PointItem::Arc(arc) => PointItem::Arc(Arc {
+            start: point(&arc.start),
+            mid: point(&arc.mid),
+            end: point(&arc.end),
+          }),
+        })
+        .collect(),
+    )
+  }
+}
+
+impl ToSExpr for PointList {
+  fn to_sexpr(&self) -> SExpr {
+    let mut list = SExprList::named("pts");
+    for item in &self.0 {
+      list = list.with(match item {
+        PointItem::Point(point) => point.to_named_sexpr("xy"),
+        PointItem::Arc(arc) => arc.to_sexpr(),
+      });
+    }
+    list.as_sexpr()
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct BoundingBox {
@@ -381,6 +556,11 @@ pub trait GetBoundingBox {
   fn bounding_box(&self) -> BoundingBox;
 }
 
+/// Geometric area of a closed shape in mm², ignoring the stroke width.
+pub trait Area {
+  fn area(&self) -> f64;
+}
+
 impl Default for BoundingBox {
   fn default() -> Self {
     BoundingBox {
@@ -459,10 +639,11 @@ impl BoundingBox {
     }
   }
 
-  pub fn from_points(points: &[Point]) -> Self {
+  /// Returns the smallest box containing all points, or an empty box when there are none.
+  pub fn from_points(points: impl IntoIterator<Item = Point>) -> Self {
     let mut result = BoundingBox::default();
     for p in points {
-      result.add_point(p);
+      result.add_point(&p);
     }
     result
   }
@@ -474,6 +655,60 @@ impl BoundingBox {
     self.max_y = self.max_y.max(other.max_y);
   }
 
+  /// Returns the smallest box containing both boxes.
+  pub fn union(&self, other: &Self) -> BoundingBox {
+    let mut result = self.clone();
+    result.envelop(other);
+    result
+  }
+
+  /// Returns the area shared by both boxes, or `None` when they do not overlap. Boxes that only
+  /// touch share a box of zero width or height.
+  pub fn intersection(&self, other: &Self) -> Option<BoundingBox> {
+    let result = BoundingBox {
+      min_x: self.min_x.max(other.min_x),
+      min_y: self.min_y.max(other.min_y),
+      max_x: self.max_x.min(other.max_x),
+      max_y: self.max_y.min(other.max_y),
+    };
+
+    (!result.is_empty()).then_some(result)
+  }
+
+  /// Whether both boxes overlap or touch. Empty boxes intersect nothing.
+  pub fn intersects(&self, other: &BoundingBox) -> bool {
+    self.intersection(other).is_some()
+  }
+
+  /// Whether the point lies inside the box or on its edge. Empty boxes contain nothing.
+  pub fn contains_point(&self, x: f64, y: f64) -> bool {
+    (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+  }
+
+  /// Whether the box contains no points, like the default box.
+  pub fn is_empty(&self) -> bool {
+    self.min_x > self.max_x || self.min_y > self.max_y
+  }
+
+  /// Returns the box grown by `margin` on all sides. Empty boxes stay empty.
+  pub fn expanded(&self, margin: f64) -> BoundingBox {
+    if self.is_empty() {
+      return self.clone();
+    }
+
+    BoundingBox {
+      min_x: self.min_x - margin,
+      min_y: self.min_y - margin,
+      max_x: self.max_x + margin,
+      max_y: self.max_y + margin,
+    }
+  }
+
+  /// Returns the box shrunk by `margin` on all sides. Empty boxes stay empty.
+  pub fn inset(&self, margin: f64) -> BoundingBox {
+    self.expanded(-margin)
+  }
+
   pub fn move_by(&mut self, dx: f64, dy: f64) {
     self.min_x += dx;
     self.min_y += dy;
@@ -482,6 +717,18 @@ impl BoundingBox {
   }
 }
 
+impl FromIterator<BoundingBox> for BoundingBox {
+  /// Envelops all boxes, giving an empty box for an empty iterator.
+  fn from_iter<T: IntoIterator<Item = BoundingBox>>(iter: T) -> Self {
+    iter
+      .into_iter()
+      .fold(BoundingBox::default(), |mut result, bbox| {
+        result.envelop(&bbox);
+        result
+      })
+  }
+}
+
 impl Display for BoundingBox {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     let x = self.x();
@@ -0,0 +1,76 @@
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Sheet metadata shown in the drawing frame
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TitleBlock {
+  pub title: Option<String>,
+  pub date: Option<String>,
+  /// Revision, e.g. `A`
+  pub rev: Option<String>,
+  pub company: Option<String>,
+  /// Free-form comment lines with their number, 1 to 9
+  pub comments: Vec<(u32, String)>,
+}
+
+impl TryFrom<SExpr> for TitleBlock {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "title_block", "TitleBlock::try_from");
+
+    let mut title_block = TitleBlock::default();
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "title" => title_block.title = Some(read_text(attr.discard(1)?)?),
+        "date" => title_block.date = Some(read_text(attr.discard(1)?)?),
+        "rev" => title_block.rev = Some(read_text(attr.discard(1)?)?),
+        "company" => title_block.company = Some(read_text(attr.discard(1)?)?),
+        "comment" => {
+          let number = attr.discard(1)?.next_into()?;
+          title_block.comments.push((number, read_text(&mut attr)?));
+        }
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(title_block)
+  }
+}
+
+/// Reads a text field, which KiCad 5 leaves unquoted when it has no spaces.
+fn read_text(list: &mut SExprList) -> Result<String, ParserError> {
+  match list.next_any()? {
+    SExpr::Value(value) => Ok(value.0),
+    SExpr::Symbol(symbol) => Ok(symbol.0),
+    got => Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+  }
+}
+
+impl ToSExpr for TitleBlock {
+  fn to_sexpr(&self) -> SExpr {
+    let text = |name: &str, value: &Option<String>| {
+      value
+        .as_ref()
+        .map(|value| SExpr::named(name, SExpr::value(value)))
+    };
+
+    SExprList::named("title_block")
+      .with_maybe(text("title", &self.title))
+      .with_maybe(text("date", &self.date))
+      .with_maybe(text("rev", &self.rev))
+      .with_maybe(text("company", &self.company))
+      .with_all(self.comments.iter().map(|(number, comment)| {
+        SExprList::named("comment")
+          .with((*number).into())
+          .with(SExpr::value(comment))
+          .as_sexpr()
+      }))
+      .as_sexpr()
+  }
+}
@@ -0,0 +1,474 @@
+use crate::{
+  common::{FillType, Layer, PointList, Uuid, layer_list},
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Copper pour or rule area, on the board or inside a footprint
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Zone {
+  /// Ordinal of the net the zone is connected to, 0 when unconnected
+  pub net: i32,
+  /// Name of the net the zone is connected to
+  pub net_name: String,
+  /// Layers the zone resides on
+  pub layers: Vec<Layer>,
+  /// Unique identifier
+  pub uuid: Uuid,
+  /// Optional user defined name
+  pub name: Option<String>,
+  /// Outline hatching shown in the editor
+  pub hatch: Option<ZoneHatch>,
+  /// Pad connection settings
+  pub connect_pads: Option<ZoneConnectPads>,
+  /// Fill priority, higher priority zones are filled first
+  pub priority: Option<u32>,
+  /// Minimum fill thickness
+  pub min_thickness: Option<f64>,
+  /// Whether the fill outline is drawn with the minimum thickness, written as `no` since version 6
+  pub filled_areas_thickness: Option<bool>,
+  /// Restrictions of a rule area, `None` for copper pours
+  pub keepout: Option<ZoneKeepout>,
+  /// Fill style, `None` when the zone has no `fill` list
+  pub fill: Option<FillType>,
+  /// Thermal relief, smoothing and hatch settings written inside `fill`
+  pub fill_settings: ZoneFillSettings,
+  /// Outline of the zone
+  pub polygon: PointList,
+  /// Copper fill computed by the last zone refill
+  pub filled_polygons: Vec<ZoneFilledPolygon>,
+  /// Unmodeled settings, kept when parsing with
+  /// [`collect_unknown`](crate::parser::ParseOptions::collect_unknown)
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub other: Vec<SExpr>,
+}
+
+/// Settings written next to the [`FillType`] in the `fill` list of a zone
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ZoneFillSettings {
+  /// Clearance around pads connected with thermal reliefs
+  pub thermal_gap: Option<f64>,
+  /// Width of the thermal relief spokes
+  pub thermal_bridge_width: Option<f64>,
+  /// Corner smoothing of the fill
+  pub smoothing: Option<ZoneSmoothing>,
+  /// Chamfer size or fillet radius of the smoothing
+  pub radius: Option<f64>,
+  /// Island removal: 0 removes all islands, 1 none, 2 those below `island_area_min`
+  pub island_removal_mode: Option<u8>,
+  /// Smallest island kept, in mm²
+  pub island_area_min: Option<f64>,
+  /// Angle of the hatch lines, in degrees
+  pub hatch_orientation: Option<f64>,
+  pub hatch_smoothing_level: Option<u8>,
+  pub hatch_smoothing_value: Option<f64>,
+  /// Thickness used for the hatch outline, `min_thickness` or `hatch_thickness`
+  pub hatch_border_algorithm: Option<String>,
+  /// Smallest hatch hole kept, in mm²
+  pub hatch_min_hole_area: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ZoneSmoothing {
+  Chamfer,
+  Fillet,
+}
+
+/// What a rule area forbids, `(keepout (tracks not_allowed) (vias allowed) ...)`
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ZoneKeepout {
+  pub allow_tracks: bool,
+  pub allow_vias: bool,
+  pub allow_pads: bool,
+  pub allow_copperpour: bool,
+  pub allow_footprints: bool,
+}
+
+/// Outline hatching of a zone, `(hatch edge 0.5)`
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ZoneHatch {
+  pub style: ZoneHatchStyle,
+  /// Distance between the hatch lines
+  pub pitch: f64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ZoneHatchStyle {
+  None,
+  #[default]
+  Edge,
+  Full,
+}
+
+/// How pads inside the zone connect to it
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ZoneConnectPads {
+  /// Connection type, thermal reliefs when unset
+  pub connection: Option<ZonePadConnection>,
+  /// Clearance between the zone and pads of other nets
+  pub clearance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ZonePadConnection {
+  /// Pads are connected with solid copper, `yes`
+  Solid,
+  /// Pads are not connected, `no`
+  None,
+  /// Only through-hole pads are connected with solid copper, `thru_hole_only`
+  ThroughHoleOnly,
+}
+
+/// One filled area of a zone on a single layer
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ZoneFilledPolygon {
+  pub layer: Layer,
+  /// Whether the area is an island not connected to the rest of the net
+  pub island: bool,
+  pub points: PointList,
+}
+
+impl TryFrom<SExpr> for Zone {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "zone", "Zone::try_from");
+
+    let mut zone = Zone::default();
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "net" => zone.net = attr.read_named()?,
+        "net_name" => zone.net_name = attr.read_named()?,
+        "layer" => zone.layers = vec![attr.as_sexpr_into()?],
+        "layers" => zone.layers = attr.as_sexpr_into()?,
+        "uuid" => zone.uuid = attr.as_sexpr_into()?,
+        "name" => zone.name = Some(attr.read_named()?),
+        "hatch" => {
+          let style = match attr.discard(1)?.next_symbol()?.as_str() {
+            "none" => ZoneHatchStyle::None,
+            "edge" => ZoneHatchStyle::Edge,
+            "full" => ZoneHatchStyle::Full,
+            found => crate::error!("Valid zone hatch style", found),
+          };
+          let pitch = attr.next_into()?;
+          attr.expect_end()?;
+          zone.hatch = Some(ZoneHatch { style, pitch });
+        }
+        "connect_pads" => zone.connect_pads = Some(attr.as_sexpr_into()?),
+        "priority" => zone.priority = Some(attr.read_named()?),
+        "min_thickness" => zone.min_thickness = Some(attr.read_named()?),
+        "filled_areas_thickness" => zone.filled_areas_thickness = Some(attr.read_yes_no()?),
+        "keepout" => zone.keepout = Some(attr.as_sexpr_into()?),
+        // The style and the settings share the list, each parser skips the other's tokens
+        "fill" => {
+          let fill = attr.as_sexpr();
+          zone.fill = Some(fill.clone().try_into()?);
+          zone.fill_settings = fill.try_into()?;
+        }
+        "polygon" => zone.polygon = attr.discard(1)?.next_into()?,
+        "filled_polygon" => zone.filled_polygons.push(attr.as_sexpr_into()?),
+
+        _ if crate::parser::parse_options().collect_unknown => zone.other.push(attr.as_sexpr()),
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(zone)
+  }
+}
+
+impl ToSExpr for Zone {
+  fn to_sexpr(&self) -> SExpr {
+    let layers = match self.layers.as_slice() {
+      [layer] => layer.to_sexpr(),
+      layers => layer_list("layers", layers),
+    };
+
+    SExprList::named("zone")
+      .with(SExpr::named("net", self.net))
+      .with(SExpr::named("net_name", SExpr::value(&self.net_name)))
+      .with(layers)
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .with_maybe(
+        self
+          .name
+          .as_ref()
+          .map(|name| SExpr::named("name", SExpr::value(name))),
+      )
+      .with_maybe(self.hatch.as_ref().map(|hatch| {
+        let style = match hatch.style {
+          ZoneHatchStyle::None => "none",
+          ZoneHatchStyle::Edge => "edge",
+          ZoneHatchStyle::Full => "full",
+        };
+        SExprList::named("hatch")
+          .with(SExpr::symbol(style))
+          .with(hatch.pitch.into())
+          .as_sexpr()
+      }))
+      .with_maybe(
+        self
+          .priority
+          .map(|priority| SExpr::named("priority", priority)),
+      )
+      .with_maybe(self.connect_pads.as_ref().map(ZoneConnectPads::to_sexpr))
+      .with_maybe(
+        self
+          .min_thickness
+          .map(|thickness| SExpr::named("min_thickness", thickness)),
+      )
+      .with_maybe(
+        self
+          .filled_areas_thickness
+          .map(|thickness| SExpr::named("filled_areas_thickness", SExpr::yes_no(thickness))),
+      )
+      .with_maybe(self.keepout.as_ref().map(ZoneKeepout::to_sexpr))
+      .with_maybe(
+        self
+          .fill
+          .as_ref()
+          .map(|fill| self.fill_settings.to_fill_sexpr(fill)),
+      )
+      .with_all(self.other.iter().cloned())
+      .with(SExpr::named("polygon", self.polygon.to_sexpr()))
+      .with_all(self.filled_polygons.iter().map(ZoneFilledPolygon::to_sexpr))
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for ZoneKeepout {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "keepout", "ZoneKeepout::try_from");
+
+    let mut keepout = ZoneKeepout::default();
+    while let Some(mut attr) = list.next_maybe_list()? {
+      let name = attr.next_symbol()?;
+      let allowed = match attr.next_symbol()?.as_str() {
+        "allowed" => true,
+        "not_allowed" => false,
+        other => crate::error!("allowed | not_allowed", other),
+      };
+
+      match name.as_str() {
+        "tracks" => keepout.allow_tracks = allowed,
+        "vias" => keepout.allow_vias = allowed,
+        "pads" => keepout.allow_pads = allowed,
+        "copperpour" => keepout.allow_copperpour = allowed,
+        "footprints" => keepout.allow_footprints = allowed,
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(keepout)
+  }
+}
+
+impl ToSExpr for ZoneKeepout {
+  fn to_sexpr(&self) -> SExpr {
+    let rule = |name: &str, allowed: bool| {
+      SExpr::named(
+        name,
+        SExpr::symbol(if allowed { "allowed" } else { "not_allowed" }),
+      )
+    };
+
+    SExprList::named("keepout")
+      .with(rule("tracks", self.allow_tracks))
+      .with(rule("vias", self.allow_vias))
+      .with(rule("pads", self.allow_pads))
+      .with(rule("copperpour", self.allow_copperpour))
+      .with(rule("footprints", self.allow_footprints))
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for ZoneFillSettings {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(list.next_symbol()?, "fill", "ZoneFillSettings::try_from");
+
+    let mut settings = ZoneFillSettings::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        // The fill style is read by `FillType`
+        SExpr::Symbol(_) => {}
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "mode" | "hatch_gap" | "hatch_thickness" => {}
+          "thermal_gap" => settings.thermal_gap = Some(attr.read_named()?),
+          "thermal_bridge_width" => settings.thermal_bridge_width = Some(attr.read_named()?),
+          "smoothing" => {
+            settings.smoothing = match attr.discard(1)?.next_symbol()?.as_str() {
+              "chamfer" => Some(ZoneSmoothing::Chamfer),
+              "fillet" => Some(ZoneSmoothing::Fillet),
+              "none" => None,
+              found => crate::error!("chamfer | fillet | none", found),
+            }
+          }
+          "radius" => settings.radius = Some(attr.read_named()?),
+          "island_removal_mode" => settings.island_removal_mode = Some(attr.read_named()?),
+          "island_area_min" => settings.island_area_min = Some(attr.read_named()?),
+          "hatch_orientation" => settings.hatch_orientation = Some(attr.read_named()?),
+          "hatch_smoothing_level" => settings.hatch_smoothing_level = Some(attr.read_named()?),
+          "hatch_smoothing_value" => settings.hatch_smoothing_value = Some(attr.read_named()?),
+          "hatch_border_algorithm" => {
+            settings.hatch_border_algorithm = Some(attr.discard(1)?.next_symbol()?.0)
+          }
+          "hatch_min_hole_area" => settings.hatch_min_hole_area = Some(attr.read_named()?),
+          name => crate::catch_all!(name),
+        },
+        other => crate::catch_all!(other),
+      }
+    }
+
+    Ok(settings)
+  }
+}
+
+impl ZoneFillSettings {
+  /// Writes the `fill` list of a zone with the given style, `(fill yes (mode hatch) ...)`.
+  pub fn to_fill_sexpr(&self, fill: &FillType) -> SExpr {
+    let named = |name: &str, value: Option<f64>| value.map(|value| SExpr::named(name, value));
+    let (hatch_gap, hatch_thickness) = match fill {
+      FillType::Hatch { spacing, thickness } => (*spacing, *thickness),
+      _ => (None, None),
+    };
+
+    SExprList::named("fill")
+      .with_maybe(fill.is_filled().then(|| SExpr::symbol("yes")))
+      .with_maybe(
+        matches!(fill, FillType::Hatch { .. })
+          .then(|| SExpr::named("mode", SExpr::symbol("hatch"))),
+      )
+      .with_maybe(named("thermal_gap", self.thermal_gap))
+      .with_maybe(named("thermal_bridge_width", self.thermal_bridge_width))
+      .with_maybe(self.smoothing.as_ref().map(|smoothing| {
+        let smoothing = match smoothing {
+          ZoneSmoothing::Chamfer => "chamfer",
+          ZoneSmoothing::Fillet => "fillet",
+        };
+        SExpr::named("smoothing", SExpr::symbol(smoothing))
+      }))
+      .with_maybe(named("radius", self.radius))
+      .with_maybe(
+        self
+          .island_removal_mode
+          .map(|mode| SExpr::named("island_removal_mode", mode)),
+      )
+      .with_maybe(named("island_area_min", self.island_area_min))
+      .with_maybe(named("hatch_thickness", hatch_thickness))
+      .with_maybe(named("hatch_gap", hatch_gap))
+      .with_maybe(named("hatch_orientation", self.hatch_orientation))
+      .with_maybe(
+        self
+          .hatch_smoothing_level
+          .map(|level| SExpr::named("hatch_smoothing_level", level)),
+      )
+      .with_maybe(named("hatch_smoothing_value", self.hatch_smoothing_value))
+      .with_maybe(
+        self
+          .hatch_border_algorithm
+          .as_ref()
+          .map(|algorithm| SExpr::named("hatch_border_algorithm", SExpr::symbol(algorithm))),
+      )
+      .with_maybe(named("hatch_min_hole_area", self.hatch_min_hole_area))
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for ZoneConnectPads {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "connect_pads",
+      "ZoneConnectPads::try_from"
+    );
+
+    let mut connect = ZoneConnectPads::default();
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(s) if s == "yes" => connect.connection = Some(ZonePadConnection::Solid),
+        SExpr::Symbol(s) if s == "no" => connect.connection = Some(ZonePadConnection::None),
+        SExpr::Symbol(s) if s == "thru_hole_only" => {
+          connect.connection = Some(ZonePadConnection::ThroughHoleOnly)
+        }
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "clearance" => connect.clearance = attr.read_named()?,
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(connect)
+  }
+}
+
+impl ToSExpr for ZoneConnectPads {
+  fn to_sexpr(&self) -> SExpr {
+    let connection = self.connection.as_ref().map(|connection| {
+      SExpr::symbol(match connection {
+        ZonePadConnection::Solid => "yes",
+        ZonePadConnection::None => "no",
+        ZonePadConnection::ThroughHoleOnly => "thru_hole_only",
+      })
+    });
+
+    SExprList::named("connect_pads")
+      .with_maybe(connection)
+      .with(SExpr::named("clearance", self.clearance))
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for ZoneFilledPolygon {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "filled_polygon",
+      "ZoneFilledPolygon::try_from"
+    );
+
+    let mut polygon = ZoneFilledPolygon::default();
+    while let Some(attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "layer" => polygon.layer = attr.as_sexpr_into()?,
+        "island" => polygon.island = true,
+        "pts" => polygon.points = attr.as_sexpr_into()?,
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(polygon)
+  }
+}
+
+impl ToSExpr for ZoneFilledPolygon {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("filled_polygon")
+      .with(self.layer.to_sexpr())
+      .with_maybe(self.island.then(|| SExprList::named("island").as_sexpr()))
+      .with(self.points.to_sexpr())
+      .as_sexpr()
+  }
+}
@@ -1,7 +1,11 @@
 pub mod parser;
+pub use parser::read_utf8;
 
 pub mod common;
 
 mod parser_macros;
 pub mod pcb_file;
+#[cfg(feature = "svg")]
+pub mod render;
+pub use pcb_file::parse_directory;
 pub mod sexpr;
@@ -1,6 +1,8 @@
+use std::cell::{Cell, RefCell};
+
 use backtrace::Backtrace;
 
-use crate::sexpr::SExpr;
+use crate::sexpr::{SExpr, Span};
 
 #[derive(Debug)]
 pub struct ParserError {
@@ -8,6 +10,8 @@ pub struct ParserError {
   pub expected: String,
   pub found: String,
   pub in_context: Vec<String>,
+  /// Location in the input of the innermost list that failed to convert, when known
+  pub span: Option<Span>,
   pub backtrace: Backtrace,
 }
 
@@ -19,6 +23,8 @@ pub enum ParserErrorKind {
   UnexpectedEnd,
   InvalidSExpr,
   Unexpected,
+  InvalidEncoding,
+  Io,
 }
 
 impl ParserError {
@@ -28,6 +34,7 @@ impl ParserError {
       expected: expected.into(),
       found: found.into(),
       in_context: vec![],
+      span: None,
       backtrace: Backtrace::new(),
     }
   }
@@ -38,6 +45,19 @@ impl ParserError {
       expected: expected.into(),
       found: format!("{:?}", found.into()),
       in_context: vec![],
+      span: None,
+      backtrace: Backtrace::new(),
+    }
+  }
+
+  /// Wraps an error raised while reading a file or stream.
+  pub fn io(error: std::io::Error) -> Self {
+    ParserError {
+      kind: ParserErrorKind::Io,
+      expected: "readable file".to_string(),
+      found: error.to_string(),
+      in_context: vec![],
+      span: None,
       backtrace: Backtrace::new(),
     }
   }
@@ -46,4 +66,130 @@ impl ParserError {
     self.in_context.push(context.into());
     self
   }
+
+  /// Records `span` as the error location unless a more precise one is already known.
+  pub fn or_span(mut self, span: Option<Span>) -> Self {
+    self.span = self.span.or(span);
+    self
+  }
+
+  /// Returns the 1-based line and column of the error in `input`, the text that was parsed.
+  pub fn line_column(&self, input: &str) -> Option<(usize, usize)> {
+    self.span.map(|span| span.line_column(input))
+  }
+}
+
+/// Decodes the raw content of a KiCad file as UTF-8, stripping a leading byte order mark.
+///
+/// # Errors
+///
+/// Returns a `ParserError` naming the byte offset of the first invalid sequence.
+pub fn read_utf8(bytes: &[u8]) -> Result<String, ParserError> {
+  let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+  match std::str::from_utf8(bytes) {
+    Ok(content) => Ok(content.to_string()),
+    Err(error) => Err(ParserError {
+      kind: ParserErrorKind::InvalidEncoding,
+      expected: "valid UTF-8".to_string(),
+      found: format!("invalid byte at offset {}", error.valid_up_to()),
+      in_context: vec![crate::context!()],
+      span: None,
+      backtrace: Backtrace::new(),
+    }),
+  }
+}
+
+/// Parses a whole document whose root list converts to `T`, such as a board or a footprint
+/// file. Conversion errors get the line and column of the failing list added to their context.
+pub(crate) fn parse_document<T>(input: &str, expected: &str) -> Result<T, ParserError>
+where
+  T: TryFrom<SExpr, Error = ParserError>,
+{
+  let sexprs = crate::sexpr::parse_sexpr(input).map_err(|error| ParserError {
+    found: error,
+    kind: ParserErrorKind::SExpressionError,
+    expected: expected.to_string(),
+    in_context: vec![crate::context!()],
+    span: None,
+    backtrace: Backtrace::new(),
+  })?;
+
+  sexprs
+    .as_sexpr_into()
+    .map_err(|error: ParserError| match error.line_column(input) {
+      Some((line, column)) => error.add_context(format!("input line {line}, column {column}")),
+      None => error,
+    })
+}
+
+/// Controls how tokens the crate does not model are handled, see
+/// [`crate::pcb_file::parse_pcb_file_with`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+  /// Fail on the first unknown token instead of skipping it.
+  pub strict: bool,
+  /// Keep unknown lists found directly in the board or in a footprint in their `unknown` field,
+  /// where they are written back on serialization. Collected lists are not errors in strict mode.
+  pub collect_unknown: bool,
+  /// Round every coordinate to whole nanometers, KiCad's internal resolution, so documents that
+  /// only differ in how numbers are written parse to equal values. See
+  /// [`PointFixed`](crate::common::PointFixed).
+  pub fixed_point: bool,
+}
+
+thread_local! {
+  static PARSE_OPTIONS: Cell<ParseOptions> = Cell::new(ParseOptions::default());
+  static WARNINGS: RefCell<Option<Vec<ParserError>>> = const { RefCell::new(None) };
+}
+
+/// Returns the options of the parse running on this thread.
+pub(crate) fn parse_options() -> ParseOptions {
+  PARSE_OPTIONS.with(Cell::get)
+}
+
+/// Runs `parse` with `options` in effect on this thread, restoring the previous options after,
+/// even when `parse` panics.
+pub(crate) fn with_parse_options<T>(options: ParseOptions, parse: impl FnOnce() -> T) -> T {
+  struct Restore(ParseOptions);
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      PARSE_OPTIONS.with(|current| current.set(self.0));
+    }
+  }
+
+  let _restore = Restore(PARSE_OPTIONS.with(|current| current.replace(options)));
+  parse()
+}
+
+/// Whether the parse running on this thread records warnings, see [`collect_warnings`].
+#[cfg(feature = "rayon")]
+pub(crate) fn collecting_warnings() -> bool {
+  WARNINGS.with_borrow(Option::is_some)
+}
+
+/// Records a skipped token when the parse running on this thread collects warnings. The warning
+/// is only built in that case, since errors capture a backtrace.
+pub(crate) fn warn(warning: impl FnOnce() -> ParserError) {
+  WARNINGS.with_borrow_mut(|warnings| {
+    if let Some(warnings) = warnings {
+      warnings.push(warning());
+    }
+  });
+}
+
+/// Runs `parse` and returns the warnings it recorded on this thread, restoring the previous
+/// collection after, even when `parse` panics.
+pub(crate) fn collect_warnings<T>(parse: impl FnOnce() -> T) -> (T, Vec<ParserError>) {
+  struct Restore(Option<Vec<ParserError>>);
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      let previous = self.0.take();
+      WARNINGS.with(|current| current.replace(previous));
+    }
+  }
+
+  let _restore = Restore(WARNINGS.with(|current| current.replace(Some(vec![]))));
+  let result = parse();
+  let warnings = WARNINGS.with(|current| current.take()).unwrap_or_default();
+  (result, warnings)
 }
@@ -30,9 +30,20 @@ macro_rules! expect_eq {
   };
 }
 
+/// Skips a token the parser does not model, or fails with it in
+/// [strict mode](crate::parser::ParseOptions::strict). Skipped tokens are recorded as warnings
+/// when the parse collects them.
 #[macro_export]
 macro_rules! catch_all {
-  ($name: expr) => {
-    log::debug!("Unaccounted sexpr in {}:{}: {:?}", file!(), line!(), $name)
-  };
+  ($name: expr) => {{
+    log::debug!("Unaccounted sexpr in {}:{}: {:?}", file!(), line!(), $name);
+    let unknown = || {
+      $crate::parser::ParserError::unexpected("known token", format!("{:?}", $name))
+        .add_context($crate::context!())
+    };
+    if $crate::parser::parse_options().strict {
+      return Err(unknown());
+    }
+    $crate::parser::warn(unknown);
+  }};
 }
@@ -4,47 +4,400 @@ pub use pcb_layer::*;
 mod pcb_net;
 pub use pcb_net::*;
 
+mod pcb_net_class;
+pub use pcb_net_class::*;
+
 mod pcb_property;
 pub use pcb_property::*;
 
+mod pcb_canonical;
+
 mod pcb_file_general;
 pub use pcb_file_general::*;
 
+mod pcb_plot_params;
+pub use pcb_plot_params::*;
+
+mod pcb_segment;
+pub use pcb_segment::*;
+
 mod pcb_setup;
 pub use pcb_setup::*;
 
 mod pcb_stack_settings;
 pub use pcb_stack_settings::*;
 
-use crate::{common::GetBoundingBox, parser::ParserError, sexpr::SExpr};
+mod pcb_via;
+pub use pcb_via::*;
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+  common::{
+    Dimension, EmbeddedFile, Footprint, GetBoundingBox, Graphic, Group, Layer, Pad, Paper,
+    Position, TitleBlock, Zone,
+  },
+  parser::{ParseOptions, ParserError},
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
 
 pub fn parse_pcb_file(input: &str) -> Result<PcbFile, ParserError> {
-  let sexprs = crate::sexpr::parse_sexpr(input).map_err(|error| ParserError {
-    found: error,
-    kind: crate::parser::ParserErrorKind::SExpressionError,
-    expected: "valid KiCad PCB file".to_string(),
-    in_context: vec![crate::context!()],
-    backtrace: backtrace::Backtrace::new(),
-  })?;
-
-  sexprs.as_sexpr_into()
+  parse_pcb_file_with(input, ParseOptions::default())
+}
+
+/// Parses a `.kicad_pcb` document, handling unknown tokens as set in `options`.
+pub fn parse_pcb_file_with(input: &str, options: ParseOptions) -> Result<PcbFile, ParserError> {
+  crate::parser::with_parse_options(options, || {
+    crate::parser::parse_document(input, "valid KiCad PCB file")
+  })
+}
+
+/// Parses a `.kicad_pcb` document like [`parse_pcb_file_with`], also returning the unknown
+/// tokens that were skipped, with the parser location in their context.
+///
+/// Tokens collected by [`collect_unknown`](ParseOptions::collect_unknown) are not warnings.
+pub fn parse_pcb_file_with_warnings(
+  input: &str,
+  options: ParseOptions,
+) -> Result<(PcbFile, Vec<ParserError>), ParserError> {
+  let (pcb_file, warnings) =
+    crate::parser::collect_warnings(|| parse_pcb_file_with(input, options));
+  Ok((pcb_file?, warnings))
+}
+
+/// Reads and parses the `.kicad_pcb` file at `path`.
+///
+/// A leading UTF-8 byte order mark is ignored. IO failures are reported as
+/// [`ParserErrorKind::Io`](crate::parser::ParserErrorKind::Io), with the path added to the
+/// error context.
+pub fn parse_pcb_file_from_path(path: impl AsRef<Path>) -> Result<PcbFile, ParserError> {
+  let path = path.as_ref();
+  std::fs::File::open(path)
+    .map_err(ParserError::io)
+    .and_then(parse_pcb_file_from_reader)
+    .map_err(|error| error.add_context(path.display().to_string()))
+}
+
+/// Reads a whole `.kicad_pcb` document from `reader` and parses it.
+///
+/// A leading UTF-8 byte order mark is ignored. IO failures are reported as
+/// [`ParserErrorKind::Io`](crate::parser::ParserErrorKind::Io).
+pub fn parse_pcb_file_from_reader(mut reader: impl std::io::Read) -> Result<PcbFile, ParserError> {
+  let mut bytes = Vec::new();
+  reader.read_to_end(&mut bytes).map_err(ParserError::io)?;
+  parse_pcb_file_bytes(&bytes)
+}
+
+/// Parses the raw content of a `.kicad_pcb` file.
+///
+/// A leading UTF-8 byte order mark is ignored. Content that is not valid UTF-8 is reported as
+/// [`ParserErrorKind::InvalidEncoding`](crate::parser::ParserErrorKind::InvalidEncoding).
+pub fn parse_pcb_file_bytes(bytes: &[u8]) -> Result<PcbFile, ParserError> {
+  parse_pcb_file(&crate::read_utf8(bytes)?)
+}
+
+/// Parses every file in `dir` whose extension is `extension` (e.g. `kicad_pcb`), without
+/// descending into subdirectories.
+///
+/// Failures are collected next to the successes instead of aborting, and the results are sorted
+/// by path. A directory that cannot be read is reported as a single failed entry.
+pub fn parse_directory(
+  dir: &Path,
+  extension: &str,
+) -> Vec<(PathBuf, Result<PcbFile, ParserError>)> {
+  let entries = match std::fs::read_dir(dir) {
+    Ok(entries) => entries,
+    Err(error) => {
+      let error = ParserError::io(error).add_context(crate::context!());
+      return vec![(dir.to_path_buf(), Err(error))];
+    }
+  };
+
+  let extension = extension.trim_start_matches('.');
+  let mut paths: Vec<PathBuf> = entries
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+    .collect();
+  paths.sort();
+
+  paths
+    .into_iter()
+    .map(|path| {
+      let result = parse_pcb_file_from_path(&path);
+      (path, result)
+    })
+    .collect()
+}
+
+/// Writes the board back into the text of a `.kicad_pcb` file.
+pub fn write_pcb_file(pcb_file: &PcbFile) -> String {
+  let mut out = String::new();
+  pcb_file.to_sexpr().write(&mut out, 0);
+  out.push('\n');
+  out
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbFile {
   pub version: String,
   pub generator: String,
-  pub generator_version: String,
-  pub paper: String,
+  /// Version of the generating application, absent in files written before KiCad 8.
+  pub generator_version: Option<String>,
+  pub paper: Paper,
+  pub title_block: Option<TitleBlock>,
 
   pub general: PcbFileGeneral,
   pub layers: Vec<PcbLayer>,
+  pub setup: PcbSetup,
   pub properties: Vec<PcbProperty>,
   pub nets: Vec<PcbNet>,
+  pub net_classes: Vec<NetClass>,
 
   pub footprints: Vec<crate::common::Footprint>,
   pub graphics: Vec<crate::common::Graphic>,
+  pub segments: Vec<Segment>,
+  pub vias: Vec<Via>,
+  pub zones: Vec<Zone>,
+  pub dimensions: Vec<Dimension>,
+  pub groups: Vec<Group>,
+  /// Whether the fonts used by the board are stored in it, written since version 9
+  pub embedded_fonts: Option<bool>,
+  /// Fonts, 3D models and other files stored in the board
+  pub embedded_files: Vec<EmbeddedFile>,
+
+  /// Unmodeled top-level items, kept when parsing with
+  /// [`collect_unknown`](ParseOptions::collect_unknown)
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub unknown: Vec<SExpr>,
+}
+
+#[cfg(feature = "serde")]
+impl PcbFile {
+  /// Dumps the board as JSON, e.g. for analysis outside of Rust.
+  ///
+  /// Coordinates keep the precision of the file, and layers are written by name. Raw
+  /// s-expressions such as [`PcbFile::unknown`] are not included.
+  pub fn to_json(&self) -> Result<String, serde_json::Error> {
+    serde_json::to_string(self)
+  }
+
+  /// Reads a board dumped by [`PcbFile::to_json`].
+  pub fn from_json(json: &str) -> Result<PcbFile, serde_json::Error> {
+    serde_json::from_str(json)
+  }
+}
+
+impl PcbFile {
+  /// Returns the declared layers of the given type.
+  pub fn layers_of_type(&self, layer_type: PcbLayerType) -> Vec<&PcbLayer> {
+    self
+      .layers
+      .iter()
+      .filter(|layer| layer.layer_type == layer_type)
+      .collect()
+  }
+
+  /// Returns the declared copper layers (signal, power and mixed).
+  pub fn copper_layers(&self) -> Vec<&PcbLayer> {
+    self
+      .layers
+      .iter()
+      .filter(|layer| layer.layer_type.is_copper())
+      .collect()
+  }
+
+  /// Counts the copper layers, e.g. 2 for a two-layer board.
+  ///
+  /// Layers are copper when their type is signal, power or mixed, or when their name ends in
+  /// `.Cu`.
+  pub fn copper_layer_count(&self) -> usize {
+    self
+      .layers
+      .iter()
+      .filter(|layer| layer.layer_type.is_copper() || layer.name.ends_with(".Cu"))
+      .count()
+  }
+
+  /// Finds the footprint with the given reference designator, e.g. `U3`.
+  pub fn footprint_by_reference(&self, reference: &str) -> Option<&Footprint> {
+    self
+      .footprints
+      .iter()
+      .find(|footprint| footprint.reference() == Some(reference))
+  }
+
+  /// Returns the footprints with the given value, e.g. `10k`, in board order.
+  pub fn footprints_by_value(&self, value: &str) -> Vec<&Footprint> {
+    self
+      .footprints
+      .iter()
+      .filter(|footprint| footprint.value() == Some(value))
+      .collect()
+  }
+
+  /// Resolves the members of a group to the footprints they refer to, in board order. Members
+  /// that are not footprints, such as tracks or graphics, are skipped.
+  pub fn group_members(&self, group: &Group) -> Vec<&Footprint> {
+    self
+      .footprints
+      .iter()
+      .filter(|footprint| {
+        footprint
+          .uuid
+          .as_ref()
+          .is_some_and(|uuid| group.members.contains(uuid))
+      })
+      .collect()
+  }
+
+  /// Returns every footprint pad connected to the net with the given ordinal.
+  pub fn pads_on_net(&self, ordinal: u32) -> Vec<(&Footprint, &Pad)> {
+    self
+      .footprints
+      .iter()
+      .flat_map(|footprint| footprint.pads.iter().map(move |pad| (footprint, pad)))
+      .filter(|(_, pad)| pad.net_ordinal().map(i64::from) == Some(i64::from(ordinal)))
+      .collect()
+  }
+
+  /// Reports the pads whose net disagrees with the board net table: the ordinal is not declared,
+  /// or is declared under another name.
+  pub fn pad_net_mismatches(&self) -> Vec<(&Footprint, &Pad)> {
+    self
+      .footprints
+      .iter()
+      .flat_map(|footprint| footprint.pads.iter().map(move |pad| (footprint, pad)))
+      .filter(|(_, pad)| {
+        let Some((ordinal, name)) = &pad.net else {
+          return false;
+        };
+
+        !self
+          .nets
+          .iter()
+          .any(|net| i64::from(net.ordinal) == i64::from(*ordinal) && &net.name == name)
+      })
+      .collect()
+  }
+
+  /// Returns the net ordinals declared more than once, sorted and listed once each.
+  ///
+  /// Duplicates are typically left behind by merging board files and break connectivity, as
+  /// tracks and pads refer to nets by ordinal.
+  pub fn duplicate_net_ordinals(&self) -> Vec<u32> {
+    let mut seen = std::collections::BTreeMap::new();
+    for net in &self.nets {
+      *seen.entry(net.ordinal).or_insert(0) += 1;
+    }
+
+    seen
+      .into_iter()
+      .filter(|(_, count)| *count > 1)
+      .map(|(ordinal, _)| ordinal)
+      .collect()
+  }
+
+  /// Resolves the net class of a net: an inline `net_class` on the net takes precedence over
+  /// `add_net` membership, and nets without an assignment fall back to the
+  /// [`DEFAULT_NET_CLASS`].
+  pub fn net_class_for(&self, net_name: &str) -> Option<&NetClass> {
+    let find = |name: &str| self.net_classes.iter().find(|class| class.name == name);
+
+    let inline = self
+      .nets
+      .iter()
+      .find(|net| net.name == net_name)
+      .and_then(|net| net.net_class.as_deref())
+      .and_then(find);
+
+    inline
+      .or_else(|| {
+        self
+          .net_classes
+          .iter()
+          .find(|class| class.nets.iter().any(|net| net == net_name))
+      })
+      .or_else(|| find(DEFAULT_NET_CLASS))
+  }
+
+  /// Lints for graphics that render invisibly: unfilled shapes whose effective width is zero,
+  /// on the board or inside footprints.
+  pub fn zero_width_graphics(&self) -> Vec<&Graphic> {
+    self
+      .all_graphics()
+      .map(|(graphic, _)| graphic)
+      .filter(|graphic| graphic.effective_width() == Some(0.0) && !graphic.is_filled())
+      .collect()
+  }
+
+  /// Iterates over every graphic of the board, then those of each footprint, together with the
+  /// placement that maps its coordinates to board coordinates, e.g. through
+  /// [`Position::transform_point`].
+  ///
+  /// Board graphics and graphics of unplaced footprints come with the origin, which leaves
+  /// coordinates unchanged.
+  pub fn all_graphics(&self) -> impl Iterator<Item = (&Graphic, &Position)> {
+    static ORIGIN: Position = Position {
+      x: 0.0,
+      y: 0.0,
+      angle: None,
+    };
+
+    let board = self.graphics.iter().map(|graphic| (graphic, &ORIGIN));
+    let footprints = self.footprints.iter().flat_map(|footprint| {
+      let placement = footprint.position.as_ref().unwrap_or(&ORIGIN);
+      footprint
+        .graphics
+        .iter()
+        .map(move |graphic| (graphic, placement))
+    });
+
+    board.chain(footprints)
+  }
+
+  /// Collects the content of every text and text box on the board, including those inside
+  /// footprints, together with its absolute position and layer.
+  ///
+  /// Hidden text is skipped unless `include_hidden` is set.
+  pub fn all_text(&self, include_hidden: bool) -> Vec<(String, Position, Layer)> {
+    let mut out = Vec::new();
+    for (graphic, anchor) in self.all_graphics() {
+      match graphic {
+        Graphic::Text(text) if include_hidden || !text.hide => out.push((
+          text.text.clone(),
+          anchor.transform_position(&text.position),
+          text.layer.clone(),
+        )),
+        Graphic::TextBox(text) if include_hidden || !text.effects.hide => {
+          let start = text.start.unwrap_or_default();
+          let local = Position {
+            x: start.x,
+            y: start.y,
+            angle: text.angle,
+          };
+          out.push((
+            text.text.clone(),
+            anchor.transform_position(&local),
+            text.layer.clone(),
+          ));
+        }
+        _ => {}
+      }
+    }
+
+    out
+  }
+}
+
+impl<'a> IntoIterator for &'a PcbFile {
+  type Item = &'a crate::common::Footprint;
+  type IntoIter = std::slice::Iter<'a, crate::common::Footprint>;
+
+  /// Iterates over the footprints of the board.
+  fn into_iter(self) -> Self::IntoIter {
+    self.footprints.iter()
+  }
 }
 
 impl TryFrom<SExpr> for PcbFile {
@@ -56,28 +409,83 @@ impl TryFrom<SExpr> for PcbFile {
     let mut pcb_file = PcbFile::default();
     crate::expect_eq!(list.next_symbol()?, "kicad_pcb", "PcbFile::try_from");
 
+    // Footprints are independent of each other, so with rayon they are converted in parallel
+    // once the whole board has been read
+    #[cfg(feature = "rayon")]
+    let mut footprints = vec![];
+
     while let Some(mut list) = list.next_maybe_list()? {
       match list.peek_name()? {
         "version" => {
-          let version: f64 = list.discard(1)?.next_into()?;
-          pcb_file.version = (version as u64).to_string();
+          let version: i64 = list.discard(1)?.next_into()?;
+          pcb_file.version = version.to_string();
         }
 
-        "generator" => pcb_file.generator = list.discard(1)?.next_into()?,
-        "generator_version" => pcb_file.generator_version = list.discard(1)?.next_into()?,
-        "paper" => pcb_file.paper = list.discard(1)?.next_into()?,
+        "generator" => {
+          // Before version 7 the generator was written as a bare symbol
+          pcb_file.generator = match list.discard(1)?.next_any()? {
+            SExpr::Value(value) => value.0,
+            SExpr::Symbol(symbol) => symbol.0,
+            got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+          }
+        }
+        "generator_version" => pcb_file.generator_version = Some(list.discard(1)?.next_into()?),
+        "paper" => pcb_file.paper = list.as_sexpr_into()?,
+        "title_block" => pcb_file.title_block = Some(list.as_sexpr_into()?),
 
         "general" => pcb_file.general = list.as_sexpr_into()?,
         "layers" => pcb_file.layers = list.as_sexpr_into()?,
+        "setup" => pcb_file.setup = list.as_sexpr_into()?,
+        "property" => pcb_file.properties.push(list.as_sexpr_into()?),
         "net" => pcb_file.nets.push(list.as_sexpr_into()?),
-        "footprint" => pcb_file.footprints.push(list.as_sexpr_into()?),
+        "net_class" => pcb_file.net_classes.push(list.as_sexpr_into()?),
+        #[cfg(feature = "rayon")]
+        "footprint" | "module" => footprints.push(list),
+        #[cfg(not(feature = "rayon"))]
+        "footprint" | "module" => pcb_file.footprints.push(list.as_sexpr_into()?),
+        "segment" => pcb_file.segments.push(list.as_sexpr_into()?),
+        "via" => pcb_file.vias.push(list.as_sexpr_into()?),
+        "zone" => pcb_file.zones.push(list.as_sexpr_into()?),
+        "dimension" => pcb_file.dimensions.push(list.as_sexpr_into()?),
+        "group" => pcb_file.groups.push(list.as_sexpr_into()?),
+        "embedded_fonts" => pcb_file.embedded_fonts = Some(list.read_yes_no()?),
+        "embedded_files" => pcb_file.embedded_files = list.as_sexpr_into()?,
 
         name if name.starts_with("gr_") => pcb_file.graphics.push(list.as_sexpr_into()?),
 
-        _other => {
-          // TODO: Maybe log?
-          // list.error_unexpected("named list", format!("unknown name: {name}")),
+        _ if crate::parser::parse_options().collect_unknown => {
+          pcb_file.unknown.push(SExpr::List(list))
         }
+        name => crate::catch_all!(name),
+      }
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+      use rayon::prelude::*;
+
+      // Parse options and warnings are per thread, so they are handed over to the workers and
+      // the warnings gathered back in footprint order
+      let options = crate::parser::parse_options();
+      let collecting = crate::parser::collecting_warnings();
+      let parsed: Vec<(Result<Footprint, ParserError>, Vec<ParserError>)> = footprints
+        .into_par_iter()
+        .map(|footprint| {
+          crate::parser::with_parse_options(options, || {
+            if collecting {
+              crate::parser::collect_warnings(|| footprint.as_sexpr_into())
+            } else {
+              (footprint.as_sexpr_into(), vec![])
+            }
+          })
+        })
+        .collect();
+
+      for (footprint, warnings) in parsed {
+        for warning in warnings {
+          crate::parser::warn(|| warning);
+        }
+        pcb_file.footprints.push(footprint?);
       }
     }
 
@@ -85,6 +493,55 @@ impl TryFrom<SExpr> for PcbFile {
   }
 }
 
+impl ToSExpr for PcbFile {
+  fn to_sexpr(&self) -> SExpr {
+    let version = match self.version.parse::<i64>() {
+      Ok(version) => SExpr::Int(version),
+      Err(_) => SExpr::symbol(&self.version),
+    };
+
+    SExprList::named("kicad_pcb")
+      .with(SExpr::named("version", version))
+      .with(SExpr::named("generator", SExpr::value(&self.generator)))
+      .with_maybe(
+        self
+          .generator_version
+          .clone()
+          .map(|version| SExpr::named("generator_version", SExpr::value(version))),
+      )
+      .with(self.general.to_sexpr())
+      .with(self.paper.to_sexpr())
+      .with_maybe(self.title_block.as_ref().map(TitleBlock::to_sexpr))
+      .with(self.layers.to_sexpr())
+      .with(self.setup.to_sexpr())
+      .with_all(self.properties.iter().map(PcbProperty::to_sexpr))
+      .with_all(self.nets.iter().map(PcbNet::to_sexpr))
+      .with_all(self.net_classes.iter().map(NetClass::to_sexpr))
+      .with_all(self.footprints.iter().map(ToSExpr::to_sexpr))
+      .with_all(
+        self
+          .graphics
+          .iter()
+          .map(|graphic| graphic.to_prefixed_sexpr("gr")),
+      )
+      .with_all(self.dimensions.iter().map(Dimension::to_sexpr))
+      .with_all(self.segments.iter().map(Segment::to_sexpr))
+      .with_all(self.vias.iter().map(Via::to_sexpr))
+      .with_all(self.zones.iter().map(Zone::to_sexpr))
+      .with_all(self.groups.iter().map(Group::to_sexpr))
+      .with_maybe(
+        self
+          .embedded_fonts
+          .map(|embedded| SExpr::named("embedded_fonts", SExpr::yes_no(embedded))),
+      )
+      .with_maybe((!self.embedded_files.is_empty()).then(|| self.embedded_files.to_sexpr()))
+      .with_all(self.unknown.iter().cloned())
+      .as_sexpr()
+  }
+}
+
+/// Board extent: the `Edge.Cuts` outline plus every track segment and via, so copper routed
+/// outside the outline is still covered.
 impl GetBoundingBox for PcbFile {
   fn bounding_box(&self) -> crate::common::BoundingBox {
     let mut bounding = crate::common::BoundingBox::default();
@@ -96,6 +553,14 @@ impl GetBoundingBox for PcbFile {
       bounding.envelop(&graphics.bounding_box());
     }
 
+    for segment in &self.segments {
+      bounding.envelop(&segment.bounding_box());
+    }
+
+    for via in &self.vias {
+      bounding.envelop(&via.bounding_box());
+    }
+
     bounding
   }
 }
@@ -0,0 +1,136 @@
+use super::PcbFile;
+use crate::common::{Graphic, Point, PointItem, PointList, Position, Zone, mm_to_nm, nm_to_mm};
+
+impl PcbFile {
+  /// Rewrites the board into a canonical form, so boards that only differ in item order or float
+  /// noise serialize identically, e.g. for golden-file tests.
+  ///
+  /// Nets and layers are sorted by ordinal, footprints by reference, and pads, graphics, tracks,
+  /// vias, zones, groups and group members by UUID, including the graphics and zones inside
+  /// footprints. Coordinates are rounded to whole nanometers, KiCad's resolution.
+  pub fn canonicalize(&mut self) {
+    self.nets.sort_by_key(|net| net.ordinal);
+    self.layers.sort_by_key(|layer| layer.ordinal);
+    self.properties.sort_by(|a, b| a.key.cmp(&b.key));
+    self.net_classes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for footprint in &mut self.footprints {
+      if let Some(position) = &mut footprint.position {
+        round_position(position);
+      }
+
+      footprint.pads.sort_by(|a, b| a.uuid.0.cmp(&b.uuid.0));
+      for pad in &mut footprint.pads {
+        round_position(&mut pad.position);
+        round(&mut pad.size.0);
+        round(&mut pad.size.1);
+      }
+
+      footprint
+        .graphics
+        .sort_by(|a, b| a.uuid().0.cmp(&b.uuid().0));
+      footprint.graphics.iter_mut().for_each(round_graphic);
+
+      canonicalize_zones(&mut footprint.zones);
+    }
+    self.footprints.sort_by(|a, b| {
+      a.reference().cmp(&b.reference()).then_with(|| {
+        a.uuid
+          .as_ref()
+          .map(|uuid| &uuid.0)
+          .cmp(&b.uuid.as_ref().map(|uuid| &uuid.0))
+      })
+    });
+
+    self.graphics.sort_by(|a, b| a.uuid().0.cmp(&b.uuid().0));
+    self.graphics.iter_mut().for_each(round_graphic);
+
+    self.segments.sort_by(|a, b| a.uuid.0.cmp(&b.uuid.0));
+    for segment in &mut self.segments {
+      round_point(&mut segment.start);
+      round_point(&mut segment.end);
+      round(&mut segment.width);
+    }
+
+    self.vias.sort_by(|a, b| a.uuid.0.cmp(&b.uuid.0));
+    for via in &mut self.vias {
+      round_point(&mut via.position);
+      round(&mut via.size);
+      round(&mut via.drill);
+    }
+
+    self.groups.sort_by(|a, b| a.uuid.0.cmp(&b.uuid.0));
+    for group in &mut self.groups {
+      group.members.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    canonicalize_zones(&mut self.zones);
+  }
+}
+
+fn canonicalize_zones(zones: &mut [Zone]) {
+  zones.sort_by(|a, b| a.uuid.0.cmp(&b.uuid.0));
+  for zone in zones {
+    round_points(&mut zone.polygon);
+    for filled in &mut zone.filled_polygons {
+      round_points(&mut filled.points);
+    }
+  }
+}
+
+fn round(value: &mut f64) {
+  *value = nm_to_mm(mm_to_nm(*value));
+}
+
+fn round_point(point: &mut Point) {
+  round(&mut point.x);
+  round(&mut point.y);
+}
+
+fn round_position(position: &mut Position) {
+  round(&mut position.x);
+  round(&mut position.y);
+}
+
+fn round_points(points: &mut PointList) {
+  for item in &mut points.0 {
+    match item {
+      PointItem::Point(point) => round_point(point),
+      PointItem::Arc(arc) => {
+        round_point(&mut arc.start);
+        round_point(&mut arc.mid);
+        round_point(&mut arc.end);
+      }
+    }
+  }
+}
+
+fn round_graphic(graphic: &mut Graphic) {
+  match graphic {
+    Graphic::Text(text) => round_position(&mut text.position),
+    Graphic::TextBox(text) => {
+      text.start.iter_mut().for_each(round_point);
+      text.end.iter_mut().for_each(round_point);
+      round_points(&mut text.points);
+    }
+    Graphic::Line(line) => {
+      round_point(&mut line.start);
+      round_point(&mut line.end);
+    }
+    Graphic::Rectangle(rect) => {
+      round_point(&mut rect.start);
+      round_point(&mut rect.end);
+    }
+    Graphic::Circle(circle) => {
+      round_point(&mut circle.center);
+      round_point(&mut circle.end);
+    }
+    Graphic::Arc(arc) => {
+      round_point(&mut arc.start);
+      round_point(&mut arc.mid);
+      round_point(&mut arc.end);
+    }
+    Graphic::Polygon(poly) => round_points(&mut poly.points),
+    Graphic::Curve(curve) => round_points(&mut curve.points),
+  }
+}
@@ -1,6 +1,9 @@
-use crate::{parser::ParserError, sexpr::SExpr};
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbFileGeneral {
   /// The thickness token attribute defines the overall board thickness.
@@ -29,3 +32,11 @@ impl TryFrom<SExpr> for PcbFileGeneral {
     Ok(general)
   }
 }
+
+impl ToSExpr for PcbFileGeneral {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("general")
+      .with(SExpr::named("thickness", self.thickness))
+      .as_sexpr()
+  }
+}
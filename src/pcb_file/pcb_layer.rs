@@ -1,9 +1,9 @@
 use crate::{
   parser::ParserError,
-  sexpr::{SExpr, SExprSymbol},
+  sexpr::{SExpr, SExprList, SExprSymbol, SExprValue, ToSExpr},
 };
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbLayer {
   /// The layer ORDINAL is an integer used to associate the layer stack ordering. This is mostly to ensure correct mapping when the number of layers is increased in the future.
@@ -16,6 +16,51 @@ pub struct PcbLayer {
   pub user_name: Option<String>,
 }
 
+impl TryFrom<SExpr> for PcbLayer {
+  type Error = ParserError;
+
+  /// Parses a single `(ORDINAL "NAME" TYPE ["USER_NAME"])` entry.
+  ///
+  /// Only the ordinal has to come first. Third-party tools do not always follow KiCad's order, so
+  /// the type is recognized wherever it appears and defaults to `user` when missing, and the name
+  /// may be a bare symbol as written before version 6.
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut layer = PcbLayer {
+      ordinal: list.next_into()?,
+      ..Default::default()
+    };
+
+    let mut name = None;
+    let mut layer_type = None;
+    while let Some(token) = list.next_maybe() {
+      match token {
+        SExpr::Symbol(symbol)
+          if layer_type.is_none() && PcbLayerType::from_token(symbol.as_str()).is_some() =>
+        {
+          layer_type = PcbLayerType::from_token(symbol.as_str())
+        }
+        SExpr::Value(value) if name.is_some() => layer.user_name = Some(value.0),
+        SExpr::Value(SExprValue(value)) | SExpr::Symbol(SExprSymbol(value)) if name.is_none() => {
+          name = Some(value)
+        }
+        other => crate::catch_all!(other),
+      }
+    }
+
+    let Some(name) = name else {
+      crate::error!(
+        "PCB layer name",
+        format!("layer {} without a name", layer.ordinal)
+      );
+    };
+    layer.name = name;
+    layer.layer_type = layer_type.unwrap_or_default();
+
+    Ok(layer)
+  }
+}
+
 impl TryFrom<SExpr> for Vec<PcbLayer> {
   type Error = ParserError;
 
@@ -24,21 +69,28 @@ impl TryFrom<SExpr> for Vec<PcbLayer> {
     let mut out = Self::new();
     crate::expect_eq!(list.next_symbol()?, "layers", "PcbLayer::try_from");
 
-    while let Some(mut layer_list) = list.next_maybe_list()? {
-      // ! Keep in mind the ordering is crucial here.
-      out.push(PcbLayer {
-        ordinal: layer_list.next_into()?,
-        name: layer_list.next_into()?,
-        layer_type: layer_list.next_into()?,
-        user_name: layer_list.next_maybe_into()?,
-      });
+    while let Some(layer_list) = list.next_maybe_list()? {
+      out.push(layer_list.as_sexpr_into()?);
     }
 
     Ok(out)
   }
 }
 
-#[derive(Default, Debug, Clone)]
+impl ToSExpr for Vec<PcbLayer> {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("layers")
+      .with_all(self.iter().map(|layer| {
+        SExprList::from(vec![layer.ordinal.into(), SExpr::value(&layer.name)])
+          .with(layer.layer_type.to_sexpr())
+          .with_maybe(layer.user_name.clone().map(SExpr::value))
+          .as_sexpr()
+      }))
+      .as_sexpr()
+  }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PcbLayerType {
   #[default]
@@ -49,19 +101,44 @@ pub enum PcbLayerType {
   Signal,
 }
 
+impl PcbLayerType {
+  /// Whether layers of this type carry copper (signal, power or mixed).
+  pub fn is_copper(&self) -> bool {
+    matches!(self, Self::Signal | Self::Power | Self::Mixed)
+  }
+
+  fn from_token(token: &str) -> Option<Self> {
+    match token {
+      "user" => Some(PcbLayerType::User),
+      "jumper" => Some(PcbLayerType::Jumper),
+      "mixed" => Some(PcbLayerType::Mixed),
+      "power" => Some(PcbLayerType::Power),
+      "signal" => Some(PcbLayerType::Signal),
+      _ => None,
+    }
+  }
+}
+
 impl TryFrom<SExpr> for PcbLayerType {
   type Error = ParserError;
 
   fn try_from(value: SExpr) -> Result<Self, Self::Error> {
     let symbol: SExprSymbol = value.try_into()?;
-    match symbol.0.as_str() {
-      "user" => Ok(PcbLayerType::User),
-      "jumper" => Ok(PcbLayerType::Jumper),
-      "mixed" => Ok(PcbLayerType::Mixed),
-      "power" => Ok(PcbLayerType::Power),
-      "signal" => Ok(PcbLayerType::Signal),
-
-      found => crate::error!("Valid PCB Layer", found),
+    match PcbLayerType::from_token(symbol.as_str()) {
+      Some(layer_type) => Ok(layer_type),
+      None => crate::error!("Valid PCB Layer", symbol.0),
     }
   }
 }
+
+impl ToSExpr for PcbLayerType {
+  fn to_sexpr(&self) -> SExpr {
+    SExpr::symbol(match self {
+      PcbLayerType::User => "user",
+      PcbLayerType::Jumper => "jumper",
+      PcbLayerType::Mixed => "mixed",
+      PcbLayerType::Power => "power",
+      PcbLayerType::Signal => "signal",
+    })
+  }
+}
@@ -1,10 +1,15 @@
-use crate::{parser::ParserError, sexpr::SExpr};
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbNet {
   pub ordinal: u32,
   pub name: String,
+  /// Net class assigned inline with `(net_class "name")`
+  pub net_class: Option<String>,
 }
 
 impl TryFrom<SExpr> for PcbNet {
@@ -18,6 +23,83 @@ impl TryFrom<SExpr> for PcbNet {
     net.ordinal = list.next_into()?;
     net.name = list.next_into()?;
 
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "net_class" => net.net_class = Some(attr.read_named()?),
+        name => crate::catch_all!(name),
+      }
+    }
+
     Ok(net)
   }
 }
+
+impl ToSExpr for PcbNet {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("net")
+      .with(self.ordinal.into())
+      .with(SExpr::value(&self.name))
+      .with_maybe(
+        self
+          .net_class
+          .clone()
+          .map(|class| SExpr::named("net_class", SExpr::value(class))),
+      )
+      .as_sexpr()
+  }
+}
+
+impl PcbNet {
+  /// Returns the net name with KiCad's escape tokens such as `{slash}` replaced by the
+  /// characters they stand for.
+  pub fn display_name(&self) -> String {
+    unescape_net_name(&self.name)
+  }
+}
+
+/// Replaces KiCad's net name escape tokens (e.g. `{slash}`, `{dblquote}`) with the characters they
+/// stand for. Unknown tokens are left verbatim.
+pub fn unescape_net_name(name: &str) -> String {
+  let mut out = String::with_capacity(name.len());
+  let mut rest = name;
+  while let Some(start) = rest.find('{') {
+    out.push_str(&rest[..start]);
+    rest = &rest[start..];
+
+    let Some(end) = rest.find('}') else {
+      break;
+    };
+
+    let replacement = match &rest[1..end] {
+      "dblquote" => Some('"'),
+      "quote" => Some('\''),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "backslash" => Some('\\'),
+      "slash" => Some('/'),
+      "bar" => Some('|'),
+      "comma" => Some(','),
+      "colon" => Some(':'),
+      "space" => Some(' '),
+      "dollar" => Some('$'),
+      "tab" => Some('\t'),
+      "return" => Some('\n'),
+      "brace" => Some('{'),
+      _ => None,
+    };
+
+    match replacement {
+      Some(c) => {
+        out.push(c);
+        rest = &rest[end + 1..];
+      }
+      None => {
+        out.push('{');
+        rest = &rest[1..];
+      }
+    }
+  }
+
+  out.push_str(rest);
+  out
+}
@@ -0,0 +1,85 @@
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Name of the net class that nets belong to unless assigned elsewhere
+pub const DEFAULT_NET_CLASS: &str = "Default";
+
+/// Net class with its design rules, written in the board file prior to version 6
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NetClass {
+  pub name: String,
+  pub description: String,
+  pub clearance: Option<f64>,
+  pub trace_width: Option<f64>,
+  pub via_diameter: Option<f64>,
+  pub via_drill: Option<f64>,
+  pub micro_via_diameter: Option<f64>,
+  pub micro_via_drill: Option<f64>,
+  pub diff_pair_width: Option<f64>,
+  pub diff_pair_gap: Option<f64>,
+  /// Names of the member nets, from the `add_net` tokens
+  pub nets: Vec<String>,
+}
+
+impl TryFrom<SExpr> for NetClass {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut class = NetClass::default();
+
+    crate::expect_eq!(list.next_symbol()?, "net_class", "NetClass::try_from");
+    class.name = match list.next_any()? {
+      SExpr::Value(value) => value.0,
+      SExpr::Symbol(symbol) => symbol.0,
+      got => return Err(ParserError::unexpected_sexpr("Value or Symbol", got)),
+    };
+    class.description = list.next_into()?;
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "clearance" => class.clearance = Some(attr.read_named()?),
+        "trace_width" => class.trace_width = Some(attr.read_named()?),
+        "via_dia" => class.via_diameter = Some(attr.read_named()?),
+        "via_drill" => class.via_drill = Some(attr.read_named()?),
+        "uvia_dia" => class.micro_via_diameter = Some(attr.read_named()?),
+        "uvia_drill" => class.micro_via_drill = Some(attr.read_named()?),
+        "diff_pair_width" => class.diff_pair_width = Some(attr.read_named()?),
+        "diff_pair_gap" => class.diff_pair_gap = Some(attr.read_named()?),
+        "add_net" => class.nets.push(attr.read_named()?),
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(class)
+  }
+}
+
+impl ToSExpr for NetClass {
+  fn to_sexpr(&self) -> SExpr {
+    let rule = |name: &str, value: Option<f64>| value.map(|value| SExpr::named(name, value));
+
+    SExprList::named("net_class")
+      .with(SExpr::value(&self.name))
+      .with(SExpr::value(&self.description))
+      .with_maybe(rule("clearance", self.clearance))
+      .with_maybe(rule("trace_width", self.trace_width))
+      .with_maybe(rule("via_dia", self.via_diameter))
+      .with_maybe(rule("via_drill", self.via_drill))
+      .with_maybe(rule("uvia_dia", self.micro_via_diameter))
+      .with_maybe(rule("uvia_drill", self.micro_via_drill))
+      .with_maybe(rule("diff_pair_width", self.diff_pair_width))
+      .with_maybe(rule("diff_pair_gap", self.diff_pair_gap))
+      .with_all(
+        self
+          .nets
+          .iter()
+          .map(|net| SExpr::named("add_net", SExpr::value(net))),
+      )
+      .as_sexpr()
+  }
+}
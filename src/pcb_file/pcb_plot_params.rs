@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// The plot parameters stored in the board setup, as used by the plot and Gerber dialogs.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PcbPlotParams {
+  /// Bit mask of the layers selected for plotting.
+  pub layer_selection: Option<i64>,
+  /// Bit mask of the layers plotted on all selected layers.
+  pub plot_on_all_layers_selection: Option<i64>,
+  pub disable_aperture_macros: bool,
+  pub use_gerber_extensions: bool,
+  pub use_gerber_attributes: bool,
+  pub use_gerber_advanced_attributes: bool,
+  pub create_gerber_job_file: bool,
+  /// Number of digits after the decimal point in SVG output.
+  pub svg_precision: Option<u32>,
+  /// Plot the border and title block.
+  pub plot_frame_ref: bool,
+  /// The plot mode, 1 for filled and 2 for sketch.
+  pub mode: Option<u32>,
+  /// Use the auxiliary axis as origin.
+  pub use_aux_origin: bool,
+  pub hpgl_pen_number: Option<u32>,
+  pub hpgl_pen_speed: Option<u32>,
+  pub hpgl_pen_diameter: Option<f64>,
+  pub dxf_polygon_mode: bool,
+  pub dxf_imperial_units: bool,
+  pub dxf_use_pcbnew_font: bool,
+  pub ps_negative: bool,
+  pub ps_a4_output: bool,
+  pub plot_reference: bool,
+  pub plot_value: bool,
+  pub plot_invisible_text: bool,
+  pub plot_pad_numbers: bool,
+  pub sketch_pads_on_fab: bool,
+  pub subtract_mask_from_silk: bool,
+  /// The output format, 0 for HPGL, 1 for Gerber, 2 for PostScript, 3 for DXF, 4 for PDF and 5 for SVG.
+  pub output_format: Option<u32>,
+  pub mirror: bool,
+  /// The drill marks, 0 for none, 1 for small and 2 for actual size.
+  pub drill_shape: Option<u32>,
+  /// The scale, 0 for auto and 1 for 1:1.
+  pub scale_selection: Option<u32>,
+  pub output_directory: Option<String>,
+  /// Parameters not modelled above, retained by name so they are not lost.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub other: BTreeMap<String, SExpr>,
+}
+
+impl TryFrom<SExpr> for PcbPlotParams {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut params = PcbPlotParams::default();
+
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "pcbplotparams",
+      "PcbPlotParams::try_from"
+    );
+
+    while let Some(mut list) = list.next_maybe_list()? {
+      match list.peek_name()? {
+        "layerselection" => params.layer_selection = Some(list.read_named()?),
+        "plot_on_all_layers_selection" => {
+          params.plot_on_all_layers_selection = Some(list.read_named()?)
+        }
+        "disableapertmacros" => params.disable_aperture_macros = list.read_yes_no()?,
+        "usegerberextensions" => params.use_gerber_extensions = list.read_yes_no()?,
+        "usegerberattributes" => params.use_gerber_attributes = list.read_yes_no()?,
+        "usegerberadvancedattributes" => {
+          params.use_gerber_advanced_attributes = list.read_yes_no()?
+        }
+        "creategerberjobfile" => params.create_gerber_job_file = list.read_yes_no()?,
+        "svgprecision" => params.svg_precision = Some(list.read_named()?),
+        "plotframeref" => params.plot_frame_ref = list.read_yes_no()?,
+        "mode" => params.mode = Some(list.read_named()?),
+        "useauxorigin" => params.use_aux_origin = list.read_yes_no()?,
+        "hpglpennumber" => params.hpgl_pen_number = Some(list.read_named()?),
+        "hpglpenspeed" => params.hpgl_pen_speed = Some(list.read_named()?),
+        "hpglpendiameter" => params.hpgl_pen_diameter = Some(list.read_named()?),
+        "dxfpolygonmode" => params.dxf_polygon_mode = list.read_yes_no()?,
+        "dxfimperialunits" => params.dxf_imperial_units = list.read_yes_no()?,
+        "dxfusepcbnewfont" => params.dxf_use_pcbnew_font = list.read_yes_no()?,
+        "psnegative" => params.ps_negative = list.read_yes_no()?,
+        "psa4output" => params.ps_a4_output = list.read_yes_no()?,
+        "plotreference" => params.plot_reference = list.read_yes_no()?,
+        "plotvalue" => params.plot_value = list.read_yes_no()?,
+        "plotinvisibletext" => params.plot_invisible_text = list.read_yes_no()?,
+        "plotpadnumbers" => params.plot_pad_numbers = list.read_yes_no()?,
+        "sketchpadsonfab" => params.sketch_pads_on_fab = list.read_yes_no()?,
+        "subtractmaskfromsilk" => params.subtract_mask_from_silk = list.read_yes_no()?,
+        "outputformat" => params.output_format = Some(list.read_named()?),
+        "mirror" => params.mirror = list.read_yes_no()?,
+        "drillshape" => params.drill_shape = Some(list.read_named()?),
+        "scaleselection" => params.scale_selection = Some(list.read_named()?),
+        "outputdirectory" => params.output_directory = Some(list.read_named()?),
+
+        name => {
+          let name = name.to_string();
+          params.other.insert(name, list.as_sexpr());
+        }
+      }
+    }
+
+    Ok(params)
+  }
+}
+
+impl ToSExpr for PcbPlotParams {
+  fn to_sexpr(&self) -> SExpr {
+    let flag = |name: &str, value: bool| SExpr::named(name, SExpr::yes_no(value));
+    let number = |name: &str, value: Option<u32>| value.map(|value| SExpr::named(name, value));
+
+    SExprList::named("pcbplotparams")
+      .with_maybe(
+        self
+          .layer_selection
+          .map(|mask| SExpr::named("layerselection", SExpr::Hex(mask))),
+      )
+      .with_maybe(
+        self
+          .plot_on_all_layers_selection
+          .map(|mask| SExpr::named("plot_on_all_layers_selection", SExpr::Hex(mask))),
+      )
+      .with(flag("disableapertmacros", self.disable_aperture_macros))
+      .with(flag("usegerberextensions", self.use_gerber_extensions))
+      .with(flag("usegerberattributes", self.use_gerber_attributes))
+      .with(flag(
+        "usegerberadvancedattributes",
+        self.use_gerber_advanced_attributes,
+      ))
+      .with(flag("creategerberjobfile", self.create_gerber_job_file))
+      .with_maybe(number("svgprecision", self.svg_precision))
+      .with(flag("plotframeref", self.plot_frame_ref))
+      .with_maybe(number("mode", self.mode))
+      .with(flag("useauxorigin", self.use_aux_origin))
+      .with_maybe(number("hpglpennumber", self.hpgl_pen_number))
+      .with_maybe(number("hpglpenspeed", self.hpgl_pen_speed))
+      .with_maybe(
+        self
+          .hpgl_pen_diameter
+          .map(|diameter| SExpr::named("hpglpendiameter", diameter)),
+      )
+      .with(flag("dxfpolygonmode", self.dxf_polygon_mode))
+      .with(flag("dxfimperialunits", self.dxf_imperial_units))
+      .with(flag("dxfusepcbnewfont", self.dxf_use_pcbnew_font))
+      .with(flag("psnegative", self.ps_negative))
+      .with(flag("psa4output", self.ps_a4_output))
+      .with(flag("plotreference", self.plot_reference))
+      .with(flag("plotvalue", self.plot_value))
+      .with(flag("plotinvisibletext", self.plot_invisible_text))
+      .with(flag("plotpadnumbers", self.plot_pad_numbers))
+      .with(flag("sketchpadsonfab", self.sketch_pads_on_fab))
+      .with(flag("subtractmaskfromsilk", self.subtract_mask_from_silk))
+      .with_maybe(number("outputformat", self.output_format))
+      .with(flag("mirror", self.mirror))
+      .with_maybe(number("drillshape", self.drill_shape))
+      .with_maybe(number("scaleselection", self.scale_selection))
+      .with_maybe(
+        self
+          .output_directory
+          .as_ref()
+          .map(|directory| SExpr::named("outputdirectory", SExpr::value(directory))),
+      )
+      .with_all(self.other.values().cloned())
+      .as_sexpr()
+  }
+}
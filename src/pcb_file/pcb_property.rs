@@ -1,6 +1,35 @@
-#[derive(Default, Debug, Clone)]
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbProperty {
   pub key: String,
   pub value: String,
 }
+
+impl TryFrom<SExpr> for PcbProperty {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+
+    crate::expect_eq!(list.next_symbol()?, "property", "PcbProperty::try_from");
+    let key = list.next_into()?;
+    let value = list.next_into()?;
+    list.expect_end()?;
+
+    Ok(PcbProperty { key, value })
+  }
+}
+
+impl ToSExpr for PcbProperty {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("property")
+      .with(SExpr::value(&self.key))
+      .with(SExpr::value(&self.value))
+      .as_sexpr()
+  }
+}
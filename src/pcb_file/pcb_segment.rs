@@ -0,0 +1,67 @@
+use crate::{
+  common::{BoundingBox, GetBoundingBox, Layer, Point, Uuid},
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Straight copper track segment
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Segment {
+  pub start: Point,
+  pub end: Point,
+  /// Track width
+  pub width: f64,
+  pub layer: Layer,
+  /// Net ordinal, see [`super::PcbNet`]
+  pub net: i32,
+  pub uuid: Uuid,
+}
+
+impl TryFrom<SExpr> for Segment {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut segment = Segment::default();
+
+    crate::expect_eq!(list.next_symbol()?, "segment", "Segment::try_from");
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "start" => segment.start = attr.as_sexpr_into()?,
+          "end" => segment.end = attr.as_sexpr_into()?,
+          "width" => segment.width = attr.read_named()?,
+          "layer" => segment.layer = attr.as_sexpr_into()?,
+          "net" => segment.net = attr.read_named()?,
+          "uuid" => segment.uuid = attr.as_sexpr_into()?,
+
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(segment)
+  }
+}
+
+impl ToSExpr for Segment {
+  fn to_sexpr(&self) -> SExpr {
+    SExprList::named("segment")
+      .with(self.start.to_named_sexpr("start"))
+      .with(self.end.to_named_sexpr("end"))
+      .with(SExpr::named("width", self.width))
+      .with(self.layer.to_sexpr())
+      .with(SExpr::named("net", self.net))
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
+impl GetBoundingBox for Segment {
+  /// Bounding box of the track including its width.
+  fn bounding_box(&self) -> BoundingBox {
+    BoundingBox::from_points([self.start, self.end]).expanded(self.width / 2.0)
+  }
+}
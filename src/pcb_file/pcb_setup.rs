@@ -1,6 +1,10 @@
-use super::PcbStackUpSettings;
+use super::{PcbPlotParams, PcbStackUpSettings};
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbSetup {
   /// The optional STACK_UP_SETTINGS define the parameters required to manufacture the board.
@@ -17,4 +21,88 @@ pub struct PcbSetup {
   pub aux_axis_origin: Option<(f64, f64)>,
   /// The optional grid_origin defines the grid original if it is set to anything other than (0,0).
   pub grid_origin: Option<(f64, f64)>,
+  /// The optional plot parameters used by the plot dialog.
+  pub plot_params: Option<PcbPlotParams>,
+}
+
+impl TryFrom<SExpr> for PcbSetup {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut setup = PcbSetup::default();
+
+    crate::expect_eq!(list.next_symbol()?, "setup", "PcbSetup::try_from");
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "stackup" => setup.stack_up_settings = Some(attr.as_sexpr_into()?),
+        "pad_to_mask_clearance" => setup.pad_to_mask_clearance = attr.read_named()?,
+        "solder_mask_min_width" => setup.solder_mask_min_width = Some(attr.read_named()?),
+        "pad_to_paste_clearance" => setup.pad_to_paste_clearance = Some(attr.read_named()?),
+        "pad_to_paste_clearance_ratio" => {
+          setup.pad_to_paste_clearance_ratio = Some(attr.read_named()?)
+        }
+        "aux_axis_origin" => setup.aux_axis_origin = Some(read_origin(&mut attr)?),
+        "grid_origin" => setup.grid_origin = Some(read_origin(&mut attr)?),
+        "pcbplotparams" => setup.plot_params = Some(attr.as_sexpr_into()?),
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(setup)
+  }
+}
+
+impl ToSExpr for PcbSetup {
+  fn to_sexpr(&self) -> SExpr {
+    let origin = |name: &str, origin: Option<(f64, f64)>| {
+      origin.map(|(x, y)| {
+        SExprList::named(name)
+          .with(x.into())
+          .with(y.into())
+          .as_sexpr()
+      })
+    };
+
+    SExprList::named("setup")
+      .with_maybe(
+        self
+          .stack_up_settings
+          .as_ref()
+          .map(PcbStackUpSettings::to_sexpr),
+      )
+      .with(SExpr::named(
+        "pad_to_mask_clearance",
+        self.pad_to_mask_clearance,
+      ))
+      .with_maybe(
+        self
+          .solder_mask_min_width
+          .map(|width| SExpr::named("solder_mask_min_width", width)),
+      )
+      .with_maybe(
+        self
+          .pad_to_paste_clearance
+          .map(|clearance| SExpr::named("pad_to_paste_clearance", clearance)),
+      )
+      .with_maybe(
+        self
+          .pad_to_paste_clearance_ratio
+          .map(|ratio| SExpr::named("pad_to_paste_clearance_ratio", ratio)),
+      )
+      .with_maybe(origin("aux_axis_origin", self.aux_axis_origin))
+      .with_maybe(origin("grid_origin", self.grid_origin))
+      .with_maybe(self.plot_params.as_ref().map(PcbPlotParams::to_sexpr))
+      .as_sexpr()
+  }
+}
+
+/// Reads an `(name x y)` origin.
+fn read_origin(list: &mut SExprList) -> Result<(f64, f64), ParserError> {
+  let x = list.discard(1)?.next_into()?;
+  let y = list.next_into()?;
+  list.expect_end()?;
+  Ok((x, y))
 }
@@ -1,9 +1,13 @@
-// TODO: Implement `layer_stackup` using https://dev-docs.kicad.org/en/file-formats/sexpr-pcb/index.html#_stack_up_layer_settings
-// The layer stack up definitions is a list of layer settings for each layer required to manufacture a board including the dielectric material between the actual layers defined in the board editor.
-// layer_stackup: Vec<PcbLayerStackupSetting>,
-#[derive(Default, Debug, Clone)]
+use crate::{
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, SExprSymbol, ToSExpr},
+};
+
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PcbStackUpSettings {
+  /// The layer stack up definitions is a list of layer settings for each layer required to manufacture a board including the dielectric material between the actual layers defined in the board editor.
+  pub layers: Vec<PcbLayerStackupSetting>,
   /// The optional copper_finish token is a string that defines the copper finish used to manufacture the board.
   pub copper_finish: Option<String>,
   /// The optional dielectric_contraints token define if the board should meet all dielectric requirements.
@@ -16,10 +20,146 @@ pub struct PcbStackUpSettings {
   pub edge_plating: Option<bool>,
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum EdgeConnectorSetting {
   #[default]
   Bevelled,
   Yes,
 }
+
+/// Settings of a single layer of the stack up, see
+/// https://dev-docs.kicad.org/en/file-formats/sexpr-pcb/index.html#_stack_up_layer_settings
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PcbLayerStackupSetting {
+  /// The layer name, either a board layer such as `F.Cu` or a dielectric such as `dielectric 1`.
+  pub name: String,
+  /// The layer type, e.g. `copper`, `core` or `prepreg`.
+  pub layer_type: String,
+  /// The optional thickness of the layer in millimeters.
+  pub thickness: Option<f64>,
+  /// The optional material name, e.g. `FR4`.
+  pub material: Option<String>,
+  /// The optional dielectric constant of the layer material.
+  pub epsilon_r: Option<f64>,
+  /// The optional dielectric loss tangent of the layer material.
+  pub loss_tangent: Option<f64>,
+}
+
+impl TryFrom<SExpr> for PcbStackUpSettings {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut settings = PcbStackUpSettings::default();
+
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "stackup",
+      "PcbStackUpSettings::try_from"
+    );
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "layer" => settings.layers.push(attr.as_sexpr_into()?),
+        "copper_finish" => settings.copper_finish = Some(attr.read_named()?),
+        "dielectric_constraints" => settings.dielectric_constraints = Some(attr.read_yes_no()?),
+        "edge_connector" => {
+          let setting = attr.read_named::<SExprSymbol>()?;
+          settings.edge_connector = match setting.0.as_str() {
+            "yes" => Some(EdgeConnectorSetting::Yes),
+            "bevelled" => Some(EdgeConnectorSetting::Bevelled),
+            other => {
+              crate::catch_all!(other);
+              None
+            }
+          }
+        }
+        "castellated_pads" => settings.castellated_pads = Some(attr.read_yes_no()?),
+        "edge_plating" => settings.edge_plating = Some(attr.read_yes_no()?),
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(settings)
+  }
+}
+
+impl ToSExpr for PcbStackUpSettings {
+  fn to_sexpr(&self) -> SExpr {
+    let flag =
+      |name: &str, value: Option<bool>| value.map(|value| SExpr::named(name, SExpr::yes_no(value)));
+
+    SExprList::named("stackup")
+      .with_all(self.layers.iter().map(PcbLayerStackupSetting::to_sexpr))
+      .with_maybe(
+        self
+          .copper_finish
+          .as_ref()
+          .map(|finish| SExpr::named("copper_finish", SExpr::value(finish))),
+      )
+      .with_maybe(flag("dielectric_constraints", self.dielectric_constraints))
+      .with_maybe(self.edge_connector.as_ref().map(|setting| {
+        let setting = match setting {
+          EdgeConnectorSetting::Bevelled => "bevelled",
+          EdgeConnectorSetting::Yes => "yes",
+        };
+        SExpr::named("edge_connector", SExpr::symbol(setting))
+      }))
+      .with_maybe(flag("castellated_pads", self.castellated_pads))
+      .with_maybe(flag("edge_plating", self.edge_plating))
+      .as_sexpr()
+  }
+}
+
+impl TryFrom<SExpr> for PcbLayerStackupSetting {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut layer = PcbLayerStackupSetting::default();
+
+    crate::expect_eq!(
+      list.next_symbol()?,
+      "layer",
+      "PcbLayerStackupSetting::try_from"
+    );
+    layer.name = list.next_into()?;
+
+    while let Some(mut attr) = list.next_maybe_list()? {
+      match attr.peek_name()? {
+        "type" => layer.layer_type = attr.read_named()?,
+        "thickness" => layer.thickness = Some(attr.read_named()?),
+        "material" => layer.material = Some(attr.read_named()?),
+        "epsilon_r" => layer.epsilon_r = Some(attr.read_named()?),
+        "loss_tangent" => layer.loss_tangent = Some(attr.read_named()?),
+
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(layer)
+  }
+}
+
+impl ToSExpr for PcbLayerStackupSetting {
+  fn to_sexpr(&self) -> SExpr {
+    let number = |name: &str, value: Option<f64>| value.map(|value| SExpr::named(name, value));
+
+    SExprList::named("layer")
+      .with(SExpr::value(&self.name))
+      .with(SExpr::named("type", SExpr::value(&self.layer_type)))
+      .with_maybe(number("thickness", self.thickness))
+      .with_maybe(
+        self
+          .material
+          .as_ref()
+          .map(|material| SExpr::named("material", SExpr::value(material))),
+      )
+      .with_maybe(number("epsilon_r", self.epsilon_r))
+      .with_maybe(number("loss_tangent", self.loss_tangent))
+      .as_sexpr()
+  }
+}
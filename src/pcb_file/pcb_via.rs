@@ -0,0 +1,92 @@
+use crate::{
+  common::{BoundingBox, GetBoundingBox, Layer, Point, Uuid},
+  parser::ParserError,
+  sexpr::{SExpr, SExprList, ToSExpr},
+};
+
+/// Via types
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ViaType {
+  /// Spans all copper layers
+  #[default]
+  Through,
+  /// Spans a subset of the copper layers, `blind` token
+  BlindBuried,
+  /// Connects adjacent outer layers, `micro` token
+  Micro,
+}
+
+/// Copper via
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Via {
+  pub via_type: ViaType,
+  pub position: Point,
+  /// Pad diameter
+  pub size: f64,
+  /// Drill diameter
+  pub drill: f64,
+  /// Start and end copper layers, in file order
+  pub layers: Vec<Layer>,
+  /// Net ordinal, see [`super::PcbNet`]
+  pub net: i32,
+  pub uuid: Uuid,
+}
+
+impl TryFrom<SExpr> for Via {
+  type Error = ParserError;
+
+  fn try_from(value: SExpr) -> Result<Self, Self::Error> {
+    let mut list = value.as_list()?;
+    let mut via = Via::default();
+
+    crate::expect_eq!(list.next_symbol()?, "via", "Via::try_from");
+    while let Some(attr) = list.next_maybe() {
+      match attr {
+        SExpr::Symbol(s) if s == "blind" => via.via_type = ViaType::BlindBuried,
+        SExpr::Symbol(s) if s == "micro" => via.via_type = ViaType::Micro,
+
+        SExpr::List(mut attr) => match attr.peek_name()? {
+          "at" => via.position = attr.as_sexpr_into()?,
+          "size" => via.size = attr.read_named()?,
+          "drill" => via.drill = attr.read_named()?,
+          "layers" => via.layers = attr.as_sexpr_into()?,
+          "net" => via.net = attr.read_named()?,
+          "uuid" => via.uuid = attr.as_sexpr_into()?,
+
+          name => crate::catch_all!(name),
+        },
+        name => crate::catch_all!(name),
+      }
+    }
+
+    Ok(via)
+  }
+}
+
+impl ToSExpr for Via {
+  fn to_sexpr(&self) -> SExpr {
+    let via_type = match self.via_type {
+      ViaType::Through => None,
+      ViaType::BlindBuried => Some("blind"),
+      ViaType::Micro => Some("micro"),
+    };
+
+    SExprList::named("via")
+      .with_maybe(via_type.map(SExpr::symbol))
+      .with(self.position.to_named_sexpr("at"))
+      .with(SExpr::named("size", self.size))
+      .with(SExpr::named("drill", self.drill))
+      .with(self.layers.to_sexpr())
+      .with(SExpr::named("net", self.net))
+      .with_maybe(self.uuid.to_sexpr_if_set())
+      .as_sexpr()
+  }
+}
+
+impl GetBoundingBox for Via {
+  fn bounding_box(&self) -> BoundingBox {
+    BoundingBox::from_points([self.position]).expanded(self.size / 2.0)
+  }
+}
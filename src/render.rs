@@ -0,0 +1,273 @@
+//! Rendering of board layers to SVG, behind the `svg` feature.
+
+use svg::{
+  Document, Node,
+  node::element::{Circle, Line, Path, Rectangle, path::Data},
+};
+
+use crate::{
+  common::{BoundingBox, FillType, GetBoundingBox, Graphic, Pad, PadShape, Point, PointItem},
+  pcb_file::PcbFile,
+};
+
+/// Drawing settings of [`render_layer_with`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderStyle {
+  /// Stroke and fill color of graphics, any SVG color such as `#c83434`
+  pub color: String,
+  /// Fill color of pads
+  pub pad_color: String,
+  /// Stroke width of graphics without a width of their own, in millimeters
+  pub default_stroke_width: f64,
+}
+
+impl RenderStyle {
+  /// Uses the color KiCad's default theme gives to `layer`, see [`layer_color`].
+  pub fn for_layer(layer: &str) -> Self {
+    RenderStyle {
+      color: layer_color(layer).to_string(),
+      pad_color: layer_color(layer).to_string(),
+      default_stroke_width: 0.1,
+    }
+  }
+}
+
+/// Returns the color of KiCad's default theme for the common layers, and grey for the others.
+pub fn layer_color(layer: &str) -> &'static str {
+  match layer {
+    "F.Cu" => "#c83434",
+    "B.Cu" => "#4d7fc4",
+    name if name.starts_with("In") && name.ends_with(".Cu") => "#7fc87f",
+    "F.SilkS" | "F.Silkscreen" => "#f2eda1",
+    "B.SilkS" | "B.Silkscreen" => "#e8b2a7",
+    "F.Mask" => "#d864ff",
+    "B.Mask" => "#02ffee",
+    "F.Paste" => "#b5b5b5",
+    "B.Paste" => "#00c2c2",
+    "F.CrtYd" | "F.Courtyard" => "#ff26e2",
+    "B.CrtYd" | "B.Courtyard" => "#26e9ff",
+    "F.Fab" => "#afafaf",
+    "B.Fab" => "#585d84",
+    "Edge.Cuts" => "#d0d2cd",
+    "Cmts.User" | "User.Comments" => "#5994dc",
+    _ => "#c2c2c2",
+  }
+}
+
+/// Draws the pads and graphics of `layer`, on the board and inside footprints, with the
+/// default style of the layer. Text is not drawn.
+pub fn render_layer(pcb: &PcbFile, layer: &str) -> Document {
+  render_layer_with(pcb, layer, &RenderStyle::for_layer(layer))
+}
+
+/// Draws the pads and graphics of `layer` like [`render_layer`], with the given style.
+///
+/// The view box covers the board outline and everything drawn, in millimeters.
+pub fn render_layer_with(pcb: &PcbFile, layer: &str, style: &RenderStyle) -> Document {
+  let mut doc = Document::new();
+  let mut bounds = pcb.bounding_box();
+
+  for (graphic, placement) in pcb.all_graphics() {
+    if graphic.layer() != layer {
+      continue;
+    }
+
+    let graphic = graphic.transformed(placement);
+    if let Some(node) = graphic_node(&graphic, style) {
+      bounds.envelop(&graphic.bounding_box());
+      doc.append(node);
+    }
+  }
+
+  for footprint in &pcb.footprints {
+    let anchor = footprint.position.clone().unwrap_or_default();
+    for pad in footprint.pads.iter().filter(|pad| pad_on_layer(pad, layer)) {
+      let pad = pad.translate(&anchor, false);
+      bounds.envelop(&pad.bounding_box());
+      doc.append(pad_node(&pad, style));
+    }
+  }
+
+  if bounds.is_empty() {
+    bounds = BoundingBox {
+      min_x: 0.0,
+      min_y: 0.0,
+      max_x: 0.0,
+      max_y: 0.0,
+    };
+  }
+
+  doc.set(
+    "viewBox",
+    (bounds.min_x, bounds.min_y, bounds.width(), bounds.height()),
+  )
+}
+
+/// Whether the pad is on `layer`, including wildcards such as `*.Cu` and `F&B.Cu`.
+fn pad_on_layer(pad: &Pad, layer: &str) -> bool {
+  let Some((side, kind)) = layer.split_once('.') else {
+    return pad.layers.iter().any(|pad_layer| pad_layer == layer);
+  };
+
+  pad
+    .layers
+    .iter()
+    .any(|pad_layer| match pad_layer.as_str().split_once('.') {
+      Some(("*", pad_kind)) => pad_kind == kind,
+      Some(("F&B", pad_kind)) => pad_kind == kind && (side == "F" || side == "B"),
+      _ => pad_layer == layer,
+    })
+}
+
+fn pad_node(pad: &Pad, style: &RenderStyle) -> Box<dyn Node> {
+  let (w, h) = pad.size;
+  let center = &pad.position;
+
+  if pad.shape == PadShape::Circle {
+    return Box::new(
+      Circle::new()
+        .set("cx", center.x)
+        .set("cy", center.y)
+        .set("r", w / 2.0)
+        .set("fill", style.pad_color.as_str()),
+    );
+  }
+
+  let radius = match pad.shape {
+    PadShape::Oval => w.min(h) / 2.0,
+    PadShape::RoundedRectangle => pad.roundrect_rratio.unwrap_or(0.25) * w.min(h),
+    _ => 0.0,
+  };
+
+  // KiCad angles turn counterclockwise on screen, SVG rotations clockwise
+  Box::new(
+    Rectangle::new()
+      .set("x", center.x - w / 2.0)
+      .set("y", center.y - h / 2.0)
+      .set("width", w)
+      .set("height", h)
+      .set("rx", radius)
+      .set(
+        "transform",
+        format!(
+          "rotate({} {} {})",
+          -center.angle.unwrap_or(0.0),
+          center.x,
+          center.y
+        ),
+      )
+      .set("fill", style.pad_color.as_str()),
+  )
+}
+
+/// Builds the SVG element of a graphic already in board coordinates, `None` for text.
+fn graphic_node(graphic: &Graphic, style: &RenderStyle) -> Option<Box<dyn Node>> {
+  let width = graphic
+    .effective_width()
+    .filter(|width| *width > 0.0)
+    .unwrap_or(style.default_stroke_width);
+  let fill = |fill: &FillType| {
+    if fill.is_filled() {
+      style.color.as_str()
+    } else {
+      "none"
+    }
+  };
+
+  Some(match graphic {
+    Graphic::Text(_) | Graphic::TextBox(_) => return None,
+    Graphic::Line(line) => stroked(
+      Line::new()
+        .set("x1", line.start.x)
+        .set("y1", line.start.y)
+        .set("x2", line.end.x)
+        .set("y2", line.end.y),
+      style,
+      width,
+      "none",
+    ),
+    Graphic::Rectangle(rect) => stroked(
+      Rectangle::new()
+        .set("x", rect.start.x.min(rect.end.x))
+        .set("y", rect.start.y.min(rect.end.y))
+        .set("width", (rect.end.x - rect.start.x).abs())
+        .set("height", (rect.end.y - rect.start.y).abs()),
+      style,
+      width,
+      fill(&rect.fill),
+    ),
+    Graphic::Circle(circle) => {
+      let radius = (circle.end.x - circle.center.x).hypot(circle.end.y - circle.center.y);
+      stroked(
+        Circle::new()
+          .set("cx", circle.center.x)
+          .set("cy", circle.center.y)
+          .set("r", radius),
+        style,
+        width,
+        fill(&circle.fill),
+      )
+    }
+    Graphic::Arc(arc) => {
+      let arc = crate::common::Arc {
+        start: arc.start,
+        mid: arc.mid,
+        end: arc.end,
+      };
+      let path = Path::new().set("d", polyline(&arc.sample_points(32), false));
+      stroked(path, style, width, "none")
+    }
+    Graphic::Polygon(poly) => {
+      let points: Vec<Point> = poly
+        .points
+        .0
+        .iter()
+        .flat_map(|item| match item {
+          PointItem::Point(point) => vec![*point],
+          PointItem::Arc(arc) => arc.sample_points(16),
+        })
+        .collect();
+      let path = Path::new().set("d", polyline(&points, true));
+      stroked(path, style, width, fill(&poly.fill))
+    }
+    Graphic::Curve(curve) => {
+      let [start, c1, c2, end] = curve.points.0.as_slice() else {
+        return None;
+      };
+      let point = |item: &PointItem| match item {
+        PointItem::Point(point) => (point.x, point.y),
+        PointItem::Arc(arc) => (arc.end.x, arc.end.y),
+      };
+      let (c1, c2, end) = (point(c1), point(c2), point(end));
+      let data = Data::new()
+        .move_to(point(start))
+        .cubic_curve_to((c1.0, c1.1, c2.0, c2.1, end.0, end.1));
+      stroked(Path::new().set("d", data), style, width, "none")
+    }
+  })
+}
+
+fn stroked<T: Node + 'static>(
+  mut element: T,
+  style: &RenderStyle,
+  width: f64,
+  fill: &str,
+) -> Box<dyn Node> {
+  element.assign("stroke", style.color.as_str());
+  element.assign("stroke-width", width);
+  element.assign("stroke-linecap", "round");
+  element.assign("fill", fill);
+  Box::new(element)
+}
+
+fn polyline(points: &[Point], close: bool) -> Data {
+  let mut data = Data::new();
+  for (i, point) in points.iter().enumerate() {
+    data = if i == 0 {
+      data.move_to((point.x, point.y))
+    } else {
+      data.line_to((point.x, point.y))
+    };
+  }
+  if close { data.close() } else { data }
+}
@@ -5,21 +5,129 @@ pub use sexpr_list::SExprList;
 
 mod parse_sexpr;
 mod sexpr_list;
-pub use parse_sexpr::parse_sexpr;
+mod sexpr_reader;
+mod sexpr_ref;
+mod write_sexpr;
+pub use parse_sexpr::{parse_sexpr, parse_sexpr_borrowed};
+pub use sexpr_reader::{SExprEvent, SExprReader};
+pub use sexpr_ref::{SExprListRef, SExprRef};
+
+/// Byte range of an expression in the parsed input
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  /// Returns the 1-based line and column, counted in characters, where the span starts in
+  /// `input`.
+  pub fn line_column(&self, input: &str) -> (usize, usize) {
+    let before = input.get(..self.start).unwrap_or(input);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+      before.matches('\n').count() + 1,
+      before[line_start..].chars().count() + 1,
+    )
+  }
+}
 
 #[derive(Debug, Clone)]
 pub enum SExpr {
   List(SExprList),
   Symbol(SExprSymbol),
   Value(SExprValue),
+  /// Number written without a fraction or exponent, e.g. a net ordinal or the file version
+  Int(i64),
   Float(f64),
   Hex(i64),
+  /// Base64 payload delimited by `|`, e.g. the data of an embedded file. Line breaks inside the
+  /// bars are kept.
+  Blob(String),
+}
+
+/// Integers and floats compare by value, since KiCad writes whole floats without a fraction and
+/// `12.000000` reads back as `12`.
+impl PartialEq for SExpr {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (SExpr::List(a), SExpr::List(b)) => a == b,
+      (SExpr::Symbol(a), SExpr::Symbol(b)) => a == b,
+      (SExpr::Value(a), SExpr::Value(b)) => a == b,
+      (SExpr::Int(a), SExpr::Int(b)) => a == b,
+      (SExpr::Float(a), SExpr::Float(b)) => a == b,
+      (SExpr::Int(a), SExpr::Float(b)) | (SExpr::Float(b), SExpr::Int(a)) => *a as f64 == *b,
+      (SExpr::Hex(a), SExpr::Hex(b)) => a == b,
+      (SExpr::Blob(a), SExpr::Blob(b)) => a == b,
+      _ => false,
+    }
+  }
 }
 
 impl SExpr {
   pub fn as_list(self) -> Result<SExprList, ParserError> {
     self.try_into()
   }
+
+  pub fn symbol(symbol: impl Into<String>) -> Self {
+    SExpr::Symbol(SExprSymbol(symbol.into()))
+  }
+
+  pub fn value(value: impl Into<String>) -> Self {
+    SExpr::Value(SExprValue(value.into()))
+  }
+
+  /// Creates a single-valued named list, e.g. `(width 0.1)`.
+  pub fn named(name: &str, value: impl Into<SExpr>) -> Self {
+    SExprList::named(name).with(value.into()).as_sexpr()
+  }
+
+  /// Creates the `yes` or `no` symbol KiCad uses for flags.
+  pub fn yes_no(value: bool) -> Self {
+    SExpr::symbol(if value { "yes" } else { "no" })
+  }
+}
+
+impl From<f64> for SExpr {
+  fn from(value: f64) -> Self {
+    SExpr::Float(value)
+  }
+}
+
+impl From<f32> for SExpr {
+  /// Widens through the shortest decimal representation, so `0.1f32` is written as `0.1`.
+  fn from(value: f32) -> Self {
+    SExpr::Float(value.to_string().parse().unwrap_or(value.into()))
+  }
+}
+
+impl From<i64> for SExpr {
+  fn from(value: i64) -> Self {
+    SExpr::Int(value)
+  }
+}
+
+impl From<i32> for SExpr {
+  fn from(value: i32) -> Self {
+    SExpr::Int(value.into())
+  }
+}
+
+impl From<u32> for SExpr {
+  fn from(value: u32) -> Self {
+    SExpr::Int(value.into())
+  }
+}
+
+impl From<u8> for SExpr {
+  fn from(value: u8) -> Self {
+    SExpr::Int(value.into())
+  }
+}
+
+/// Converts a parsed item back into its KiCad s-expression representation.
+pub trait ToSExpr {
+  fn to_sexpr(&self) -> SExpr;
 }
 
 #[macro_export]
@@ -97,8 +205,10 @@ impl TryFrom<SExpr> for f64 {
 
   fn try_from(expr: SExpr) -> Result<Self, ParserError> {
     match expr {
-      SExpr::Float(d) => Ok(d),
+      SExpr::Int(i) => Ok(i as f64),
+      SExpr::Float(d) if d.is_finite() => Ok(d),
       SExpr::Hex(d) => Ok(d as f64),
+      SExpr::Float(d) => crate::error!("Finite number", d),
       expr => crate::error!(SExpr, "Value or Hex", expr),
     }
   }
@@ -109,8 +219,32 @@ impl TryFrom<SExpr> for f32 {
 
   fn try_from(expr: SExpr) -> Result<Self, ParserError> {
     match expr {
-      SExpr::Float(d) => Ok(d as f32),
+      SExpr::Int(i) => Ok(i as f32),
+      SExpr::Float(d) if d.is_finite() => Ok(d as f32),
       SExpr::Hex(d) => Ok(d as f32),
+      SExpr::Float(d) => crate::error!("Finite number", d),
+      expr => crate::error!(SExpr, "Value or Hex", expr),
+    }
+  }
+}
+
+/// Converts a float token to an integer, rejecting fractions and values outside of `i64`.
+fn whole_number(d: f64) -> Result<i64, ParserError> {
+  if d.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(&d) {
+    Ok(d as i64)
+  } else {
+    crate::error!("Whole number", d)
+  }
+}
+
+impl TryFrom<SExpr> for i64 {
+  type Error = ParserError;
+
+  fn try_from(expr: SExpr) -> Result<Self, ParserError> {
+    match expr {
+      SExpr::Int(i) => Ok(i),
+      SExpr::Float(d) => whole_number(d),
+      SExpr::Hex(d) => Ok(d),
       expr => crate::error!(SExpr, "Value or Hex", expr),
     }
   }
@@ -121,8 +255,11 @@ impl TryFrom<SExpr> for u32 {
 
   fn try_from(expr: SExpr) -> Result<Self, ParserError> {
     match expr {
-      SExpr::Float(d) => Ok(d as u32),
-      SExpr::Hex(d) => Ok(d as u32),
+      SExpr::Int(i) | SExpr::Hex(i) => match u32::try_from(i) {
+        Ok(i) => Ok(i),
+        Err(_) => crate::error!("u32", i),
+      },
+      SExpr::Float(d) => u32::try_from(SExpr::Int(whole_number(d)?)),
       expr => crate::error!(SExpr, "Value or Hex", expr),
     }
   }
@@ -133,8 +270,11 @@ impl TryFrom<SExpr> for u8 {
 
   fn try_from(expr: SExpr) -> Result<Self, ParserError> {
     match expr {
-      SExpr::Float(d) => Ok(d as u8),
-      SExpr::Hex(d) => Ok(d as u8),
+      SExpr::Int(i) | SExpr::Hex(i) => match u8::try_from(i) {
+        Ok(i) => Ok(i),
+        Err(_) => crate::error!("u8", i),
+      },
+      SExpr::Float(d) => u8::try_from(SExpr::Int(whole_number(d)?)),
       expr => crate::error!(SExpr, "Value or Hex", expr),
     }
   }
@@ -145,8 +285,11 @@ impl TryFrom<SExpr> for i32 {
 
   fn try_from(expr: SExpr) -> Result<Self, ParserError> {
     match expr {
-      SExpr::Float(d) => Ok(d as i32),
-      SExpr::Hex(d) => Ok(d as i32),
+      SExpr::Int(i) | SExpr::Hex(i) => match i32::try_from(i) {
+        Ok(i) => Ok(i),
+        Err(_) => crate::error!("i32", i),
+      },
+      SExpr::Float(d) => i32::try_from(SExpr::Int(whole_number(d)?)),
       expr => crate::error!(SExpr, "Value or Hex", expr),
     }
   }
@@ -1,21 +1,21 @@
 use nom::{
   AsChar, Err, IResult, Parser,
   branch::alt,
-  bytes::complete::{is_not, tag, take_while, take_while1},
-  character::complete::{char, one_of},
-  combinator::{cut, map},
-  error::{ContextError, ParseError, context},
+  bytes::complete::{escaped, is_not, tag, take_while, take_while_m_n, take_while1},
+  character::complete::{anychar, char, digit1, one_of, satisfy},
+  combinator::{consumed, cut, map, map_res, not, opt, recognize, rest, verify},
+  error::{ContextError, FromExternalError, ParseError, context},
   multi::separated_list0,
   number::complete::double,
   sequence::{delimited, preceded, terminated},
 };
 
 use nom_language::error::{VerboseError, convert_error};
-use std::str;
+use std::{borrow::Cow, num::ParseIntError, str};
 
 use crate::sexpr::SExprList;
 
-use super::SExpr;
+use super::{SExprListRef, SExprRef};
 
 // Parses spaces
 fn sp<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, &'a str, E> {
@@ -27,43 +27,186 @@ fn symbol<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
   i: &'a str,
 ) -> IResult<&'a str, &'a str, E> {
   take_while1(move |c: char| {
-    c.is_alphanumeric() || c == '_' || c == '-' || c == '?' || c == '!' || c == '.'
+    // `:` appears in the unquoted library links of KiCad 5
+    c.is_alphanumeric()
+      || c == '_'
+      || c == '-'
+      || c == '?'
+      || c == '!'
+      || c == '.'
+      || c == '*'
+      || c == ':'
   })(i)
 }
 
 fn quoted_string<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
   i: &'a str,
-) -> IResult<&'a str, &'a str, E> {
+) -> IResult<&'a str, Cow<'a, str>, E> {
   context(
     "string",
-    alt((
-      delimited(char('"'), is_not("\""), char('"')),
-      // Or an empty string
-      tag("\"\""),
-    )),
+    map(
+      delimited(
+        char('"'),
+        opt(escaped(is_not("\"\\"), '\\', anychar)),
+        char('"'),
+      ),
+      |raw| unescape(raw.unwrap_or_default()),
+    ),
   )
   .parse(i)
 }
 
-fn hexadecimal<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+/// Resolves the `\"`, `\\`, `\n`, `\r` and `\t` escapes of a quoted string, borrowing the input
+/// when there is nothing to replace. Unknown escapes are kept verbatim.
+fn unescape(raw: &str) -> Cow<'_, str> {
+  if !raw.contains('\\') {
+    return Cow::Borrowed(raw);
+  }
+
+  let mut out = String::with_capacity(raw.len());
+  let mut chars = raw.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      out.push(c);
+      continue;
+    }
+
+    match chars.next() {
+      Some('n') => out.push('\n'),
+      Some('r') => out.push('\r'),
+      Some('t') => out.push('\t'),
+      Some(c @ ('"' | '\\')) => out.push(c),
+      Some(c) => {
+        out.push('\\');
+        out.push(c);
+      }
+      None => out.push('\\'),
+    }
+  }
+
+  Cow::Owned(out)
+}
+
+/// Parses a `|`-delimited blob, the base64 payload of embedded files. The content may span
+/// several lines.
+fn quoted_bar<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+  i: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+  context(
+    "blob",
+    delimited(char('|'), take_while(|c| c != '|'), char('|')),
+  )
+  .parse(i)
+}
+
+fn hexadecimal<
+  'a,
+  E: ParseError<&'a str> + ContextError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+>(
   i: &'a str,
 ) -> IResult<&'a str, i64, E> {
   context(
     "hex",
-    map(
-      preceded(
-        tag("0x"),
+    preceded(
+      tag("0x"),
+      // Symbols that merely start with `0x`, such as `0xyz`, are left to `hex_like_symbol`
+      terminated(
         take_while1(|s: char| s.is_hex_digit() || s == '_'),
+        not(satisfy(char::is_alphanumeric)),
       ),
-      |raw: &str| {
-        let stripped = raw.replace('_', "");
-        i64::from_str_radix(&stripped, 16).unwrap()
-      },
+    )
+    // Values wider than 64 bits are rejected rather than truncated
+    .and_then(cut(map_res(rest, |raw: &str| {
+      u64::from_str_radix(&raw.replace('_', ""), 16).map(|value| value as i64)
+    }))),
+  )
+  .parse(i)
+}
+
+/// Parses symbols starting with `0x` that are not hexadecimal numbers, such as `0xyz`, which
+/// `integer` would otherwise split after the `0`.
+fn hex_like_symbol<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+  i: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+  recognize(preceded(tag("0x"), symbol)).parse(i)
+}
+
+/// Parses the 8-digit hexadecimal timestamps KiCad 5 writes without a prefix, such as
+/// `(tedit 5F68FEEE)`, which are read as symbols. Timestamps that also read as a number, such as
+/// `20171130` or `12345e67`, are left to `integer` and `double`.
+fn legacy_timestamp<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+  i: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+  context(
+    "timestamp",
+    verify(
+      terminated(
+        take_while_m_n(8, 8, |c: char| c.is_ascii_hexdigit()),
+        not(satisfy(char::is_alphanumeric)),
+      ),
+      |raw: &str| raw.parse::<f64>().is_err(),
+    ),
+  )
+  .parse(i)
+}
+
+/// Parses a decimal integer. Tokens with a fraction or an exponent, and integers that don't fit
+/// in 64 bits, are left to `double`.
+fn integer<
+  'a,
+  E: ParseError<&'a str> + ContextError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+>(
+  i: &'a str,
+) -> IResult<&'a str, i64, E> {
+  context(
+    "integer",
+    terminated(
+      map_res(recognize((opt(one_of("+-")), digit1)), str::parse),
+      not(one_of(".eE")),
     ),
   )
   .parse(i)
 }
 
+#[test]
+fn test_quoted_string() {
+  assert_eq!(
+    quoted_string::<VerboseError<&str>>(r#""a \"quoted\" word" rest"#),
+    Ok((" rest", Cow::Owned(r#"a "quoted" word"#.to_string())))
+  );
+  assert_eq!(
+    quoted_string::<VerboseError<&str>>(r#""line1\nline2""#),
+    Ok(("", Cow::Owned("line1\nline2".to_string())))
+  );
+  assert_eq!(
+    quoted_string::<VerboseError<&str>>(r#""back\\slash""#),
+    Ok(("", Cow::Owned("back\\slash".to_string())))
+  );
+  assert_eq!(
+    quoted_string::<VerboseError<&str>>(r#""plain""#),
+    Ok(("", Cow::Borrowed("plain")))
+  );
+  assert_eq!(
+    quoted_string::<VerboseError<&str>>(r#""""#),
+    Ok(("", Cow::Borrowed("")))
+  );
+}
+
+#[test]
+fn test_quoted_bar() {
+  assert_eq!(
+    quoted_bar::<VerboseError<&str>>("|KLUv/QBY|)"),
+    Ok((")", "KLUv/QBY"))
+  );
+  assert_eq!(
+    quoted_bar::<VerboseError<&str>>("|KLUv\n  /QBY|"),
+    Ok(("", "KLUv\n  /QBY"))
+  );
+  assert_eq!(quoted_bar::<VerboseError<&str>>("||"), Ok(("", "")));
+  assert!(quoted_bar::<VerboseError<&str>>("|KLUv").is_err());
+  assert!(quoted_bar::<VerboseError<&str>>("KLUv|").is_err());
+}
+
 #[test]
 fn test_hexadecimal() {
   assert_eq!(
@@ -74,12 +217,57 @@ fn test_hexadecimal() {
     hexadecimal::<VerboseError<&str>>("0x00000000_00000000_55555555_5755f5ff"),
     Ok(("", 0x00000000_00000000_55555555_5755f5ff))
   );
+  assert_eq!(
+    hexadecimal::<VerboseError<&str>>("0xFFFFFFFFFFFFFFFF"),
+    Ok(("", -1))
+  );
   assert!(hexadecimal::<VerboseError<&str>>("1234").is_err());
+  assert!(matches!(
+    hexadecimal::<VerboseError<&str>>("0xFFFFFFFFFFFFFFFFFF"),
+    Err(Err::Failure(_))
+  ));
+  assert!(matches!(
+    hexadecimal::<VerboseError<&str>>("0xyz"),
+    Err(Err::Error(_))
+  ));
+  assert!(matches!(
+    hexadecimal::<VerboseError<&str>>("0x1g)"),
+    Err(Err::Error(_))
+  ));
+}
+
+#[test]
+fn test_legacy_timestamp() {
+  assert_eq!(
+    legacy_timestamp::<VerboseError<&str>>("5F68FEEE)"),
+    Ok((")", "5F68FEEE"))
+  );
+  assert!(legacy_timestamp::<VerboseError<&str>>("20171130").is_err());
+  assert!(legacy_timestamp::<VerboseError<&str>>("5F68FEEE0").is_err());
+  assert!(legacy_timestamp::<VerboseError<&str>>("5F68FE").is_err());
+  assert!(legacy_timestamp::<VerboseError<&str>>("1e100000").is_err());
+  assert!(legacy_timestamp::<VerboseError<&str>>("12345e67").is_err());
+}
+
+#[test]
+fn test_integer() {
+  assert_eq!(
+    integer::<VerboseError<&str>>("20240101)"),
+    Ok((")", 20240101))
+  );
+  assert_eq!(integer::<VerboseError<&str>>("-42 "), Ok((" ", -42)));
+  assert!(integer::<VerboseError<&str>>("1.5").is_err());
+  assert!(integer::<VerboseError<&str>>("1e3").is_err());
+  assert!(integer::<VerboseError<&str>>("99999999999999999999").is_err());
+  assert!(integer::<VerboseError<&str>>("F.Cu").is_err());
 }
 
-fn list<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+fn list<
+  'a,
+  E: ParseError<&'a str> + ContextError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+>(
   i: &'a str,
-) -> IResult<&'a str, Vec<SExpr>, E> {
+) -> IResult<&'a str, Vec<SExprRef<'a>>, E> {
   context(
     "list",
     preceded(
@@ -93,29 +281,61 @@ fn list<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
   .parse(i)
 }
 
-fn sexpr<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+fn sexpr<
+  'a,
+  E: ParseError<&'a str> + ContextError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+>(
   i: &'a str,
-) -> IResult<&'a str, SExpr, E> {
+) -> IResult<&'a str, SExprRef<'a>, E> {
   preceded(
     sp,
     alt((
-      map(list, |items| SExpr::List(super::SExprList(items))),
-      map(quoted_string, |s| {
-        SExpr::Value(super::SExprValue(s.to_string()))
+      map(consumed(list), |(source, items)| {
+        SExprRef::List(SExprListRef(items, source))
       }),
-      map(hexadecimal, SExpr::Hex),
-      map(double, SExpr::Float),
-      map(symbol, |s| SExpr::Symbol(super::SExprSymbol(s.to_string()))),
+      map(quoted_string, SExprRef::Value),
+      map(quoted_bar, SExprRef::Blob),
+      map(hexadecimal, SExprRef::Hex),
+      map(hex_like_symbol, SExprRef::Symbol),
+      map(legacy_timestamp, SExprRef::Symbol),
+      map(integer, SExprRef::Int),
+      map(double, SExprRef::Float),
+      map(symbol, SExprRef::Symbol),
     )),
   )
   .parse(i)
 }
 
+/// Parses a single atom (string, number or symbol) after optional whitespace, for the streaming
+/// reader.
+pub(super) fn atom(i: &str) -> IResult<&str, SExprRef<'_>, nom::error::Error<&str>> {
+  preceded(
+    sp,
+    alt((
+      map(quoted_string, SExprRef::Value),
+      map(quoted_bar, SExprRef::Blob),
+      map(hexadecimal, SExprRef::Hex),
+      map(hex_like_symbol, SExprRef::Symbol),
+      map(legacy_timestamp, SExprRef::Symbol),
+      map(integer, SExprRef::Int),
+      map(double, SExprRef::Float),
+      map(symbol, SExprRef::Symbol),
+    )),
+  )
+  .parse(i)
+}
+
+/// Parses `input` into an owned tree. Every list records its [`super::Span`] in `input`.
 pub fn parse_sexpr(input: &str) -> Result<SExprList, String> {
+  parse_sexpr_borrowed(input).map(|list| list.to_owned_list_in(input))
+}
+
+/// Parses `input` without copying symbols and strings, for read-only inspection of large files.
+pub fn parse_sexpr_borrowed(input: &str) -> Result<SExprListRef<'_>, String> {
   match sexpr::<VerboseError<&str>>(input) {
     Ok((rest, ..)) if !rest.trim().is_empty() => Err(format!("Unparsed input: '{rest:?}'")),
 
-    Ok((.., SExpr::List(list))) => Ok(list),
+    Ok((.., SExprRef::List(list))) => Ok(list),
     Ok((.., expr)) => Err(format!("Root must be list, found: '{expr:?}'")),
 
     Err(Err::Error(e)) | Err(Err::Failure(e)) => Err(convert_error(input, e)),
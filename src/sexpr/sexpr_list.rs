@@ -1,11 +1,47 @@
+use std::collections::VecDeque;
+
 use crate::{impl_from_into, parser::ParserError, sexpr::SExprSymbol};
 
-use super::SExpr;
+use super::{SExpr, Span};
 
-#[derive(Clone, Debug)]
-pub struct SExprList(pub Vec<SExpr>);
+/// List of expressions, consumed from the front while parsing.
+///
+/// Lists produced by [`super::parse_sexpr`] remember their [`Span`] in the input, which is
+/// attached to conversion errors. The span is ignored when comparing lists.
+#[derive(Clone)]
+pub struct SExprList(pub VecDeque<SExpr>, Option<Span>);
 impl_from_into!(SExprList, SExpr::List);
 
+impl PartialEq for SExprList {
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl std::fmt::Debug for SExprList {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_tuple("SExprList").field(&self.0).finish()
+  }
+}
+
+impl From<Vec<SExpr>> for SExprList {
+  fn from(value: Vec<SExpr>) -> Self {
+    SExprList(value.into(), None)
+  }
+}
+
+impl From<VecDeque<SExpr>> for SExprList {
+  fn from(value: VecDeque<SExpr>) -> Self {
+    SExprList(value, None)
+  }
+}
+
+impl FromIterator<SExpr> for SExprList {
+  fn from_iter<I: IntoIterator<Item = SExpr>>(iter: I) -> Self {
+    SExprList(iter.into_iter().collect(), None)
+  }
+}
+
 impl<T: TryFrom<SExpr, Error = ParserError>> TryFrom<SExprList> for Vec<T> {
   type Error = ParserError;
 
@@ -20,10 +56,45 @@ impl<T: TryFrom<SExpr, Error = ParserError>> TryFrom<SExprList> for Vec<T> {
 }
 
 impl SExprList {
+  /// Creates a list starting with the symbol `name`, e.g. `(name)`.
+  pub fn named(name: &str) -> Self {
+    SExprList::from(vec![SExpr::symbol(name)])
+  }
+
+  /// Appends an element to the end of the list, returning the list for chaining.
+  pub fn with(mut self, expr: SExpr) -> Self {
+    self.0.push_back(expr);
+    self
+  }
+
+  /// Appends the element if there is one, returning the list for chaining.
+  pub fn with_maybe(self, expr: Option<SExpr>) -> Self {
+    match expr {
+      Some(expr) => self.with(expr),
+      None => self,
+    }
+  }
+
+  /// Appends all elements, returning the list for chaining.
+  pub fn with_all(mut self, exprs: impl IntoIterator<Item = SExpr>) -> Self {
+    self.0.extend(exprs);
+    self
+  }
+
   pub fn as_sexpr(self) -> SExpr {
     SExpr::List(self)
   }
 
+  /// Location of the list in the parsed input, `None` for lists built in code.
+  pub fn span(&self) -> Option<Span> {
+    self.1
+  }
+
+  pub(super) fn with_span(mut self, span: Span) -> Self {
+    self.1 = Some(span);
+    self
+  }
+
   /// Converts the entire `SExprList` into another type that implements `TryFrom<SExpr>`.
   ///
   /// This method should be used instead of `next_into` when you want to convert the whole list,
@@ -42,11 +113,15 @@ impl SExprList {
   where
     T: TryFrom<SExpr, Error = ParserError>,
   {
-    self.as_sexpr().try_into()
+    let span = self.1;
+    self
+      .as_sexpr()
+      .try_into()
+      .map_err(|error: ParserError| error.or_span(span))
   }
 
   pub fn peek_maybe(&self) -> Option<&SExpr> {
-    self.0.first()
+    self.0.front()
   }
 
   pub fn peek_name_maybe(&self) -> Result<Option<&str>, ParserError> {
@@ -63,6 +138,7 @@ impl SExprList {
       found: "end of list".to_string(),
       kind: crate::parser::ParserErrorKind::UnexpectedEnd,
       in_context: vec![crate::context!()],
+      span: self.1,
       backtrace: backtrace::Backtrace::new(),
     })
   }
@@ -86,10 +162,27 @@ impl SExprList {
       found: "end of list".to_string(),
       kind: crate::parser::ParserErrorKind::UnexpectedEnd,
       in_context: vec![crate::context!()],
+      span: self.1,
       backtrace: backtrace::Backtrace::new(),
     })
   }
 
+  /// Returns the first remaining child list whose leading symbol is `name`, e.g. `(layer ...)` for
+  /// `get("layer")`, without moving the cursor.
+  pub fn get(&self, name: &str) -> Option<&SExprList> {
+    self.0.iter().find_map(|expr| list_named(expr, name))
+  }
+
+  /// Returns every remaining child list whose leading symbol is `name`, in order, without moving
+  /// the cursor.
+  pub fn get_all(&self, name: &str) -> Vec<&SExprList> {
+    self
+      .0
+      .iter()
+      .filter_map(|expr| list_named(expr, name))
+      .collect()
+  }
+
   pub fn discard(&mut self, amount: usize) -> Result<&mut Self, ParserError> {
     if amount > self.0.len() {
       return Err(ParserError {
@@ -97,23 +190,18 @@ impl SExprList {
         found: "end of list".to_string(),
         kind: crate::parser::ParserErrorKind::UnexpectedEnd,
         in_context: vec![crate::context!()],
+        span: self.1,
         backtrace: backtrace::Backtrace::new(),
       });
     }
 
-    for _ in 0..amount {
-      self.0.remove(0);
-    }
+    self.0.drain(..amount);
 
     Ok(self)
   }
 
   pub fn next_maybe(&mut self) -> Option<SExpr> {
-    if self.0.is_empty() {
-      None
-    } else {
-      Some(self.0.remove(0))
-    }
+    self.0.pop_front()
   }
 
   pub fn next_maybe_into<T>(&mut self) -> Result<Option<T>, ParserError>
@@ -144,6 +232,7 @@ impl SExprList {
         found: "end of list".to_string(),
         kind: crate::parser::ParserErrorKind::UnexpectedEnd,
         in_context: vec![crate::context!()],
+        span: self.1,
         backtrace: backtrace::Backtrace::new(),
       })
     }
@@ -173,7 +262,53 @@ impl SExprList {
   where
     T: TryFrom<SExpr, Error = ParserError>,
   {
-    self.next_any()?.try_into()
+    let span = self.1;
+    self
+      .next_any()?
+      .try_into()
+      .map_err(|error: ParserError| error.or_span(span))
+  }
+
+  /// Reads the single value of a named list such as `(clearance 0.2)`, skipping the leading name.
+  ///
+  /// Numeric values are accepted regardless of whether they are written as an integer or a float.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `ParserError` if the value is missing, cannot be converted or is followed by more tokens.
+  pub fn read_named<T>(&mut self) -> Result<T, ParserError>
+  where
+    T: TryFrom<SExpr, Error = ParserError>,
+  {
+    let span = self.1;
+    let read = |list: &mut Self| {
+      list.discard(1)?;
+      let value = list.next_into()?;
+      list.expect_end()?;
+      Ok(value)
+    };
+    read(self).map_err(|error: ParserError| error.or_span(span))
+  }
+
+  /// Reads the flag of a named list such as `(hide yes)`, skipping the leading name.
+  ///
+  /// KiCad writes flags as `yes` / `no`, and some older files use `true` / `false`.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `ParserError` if the flag is missing, is any other token or is followed by more
+  /// tokens.
+  pub fn read_yes_no(&mut self) -> Result<bool, ParserError> {
+    let flag: SExprSymbol = self.read_named()?;
+    match flag.as_str() {
+      "yes" | "true" => Ok(true),
+      "no" | "false" => Ok(false),
+      _ => Err(
+        ParserError::unexpected_sexpr("yes or no", SExpr::Symbol(flag))
+          .add_context(crate::context!())
+          .or_span(self.1),
+      ),
+    }
   }
 
   pub fn next_symbol(&mut self) -> Result<SExprSymbol, ParserError> {
@@ -193,8 +328,19 @@ impl SExprList {
         found: format!("{:?}", self.0),
         kind: crate::parser::ParserErrorKind::Leftover,
         in_context: vec![crate::context!()],
+        span: self.1,
         backtrace: backtrace::Backtrace::new(),
       })
     }
   }
 }
+
+/// Returns `expr` as a list if it leads with the symbol `name`.
+fn list_named<'a>(expr: &'a SExpr, name: &str) -> Option<&'a SExprList> {
+  match expr {
+    SExpr::List(list) if matches!(list.0.front(), Some(SExpr::Symbol(symbol)) if symbol == &name) => {
+      Some(list)
+    }
+    _ => None,
+  }
+}
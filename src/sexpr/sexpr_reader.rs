@@ -0,0 +1,158 @@
+use super::{SExpr, SExprList, SExprRef, parse_sexpr::atom};
+
+const WHITESPACE: [char; 4] = [' ', '\t', '\r', '\n'];
+
+/// Token produced by [`SExprReader`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SExprEvent<'a> {
+  /// Opening parenthesis, with the leading symbol of the list, e.g. `footprint` for
+  /// `(footprint ...)`. `None` when the list is empty or doesn't start with a symbol.
+  ListStart(Option<&'a str>),
+  Atom(SExpr),
+  ListEnd,
+}
+
+/// Pull-based tokenizer that walks a document without building the whole tree.
+///
+/// Only the text is kept in memory, so huge boards can be processed one top-level item at a
+/// time: match on [`SExprEvent::ListStart`], then either [`SExprReader::finish_list`] the
+/// interesting items or [`SExprReader::skip_list`] the rest.
+///
+/// The reader stops after the first error.
+pub struct SExprReader<'a> {
+  input: &'a str,
+  rest: &'a str,
+  depth: usize,
+  pending: Option<SExpr>,
+  root_done: bool,
+  failed: bool,
+}
+
+impl<'a> SExprReader<'a> {
+  pub fn new(input: &'a str) -> Self {
+    SExprReader {
+      input,
+      rest: input,
+      depth: 0,
+      pending: None,
+      root_done: false,
+      failed: false,
+    }
+  }
+
+  /// Number of lists currently open, e.g. 1 directly inside `(kicad_pcb ...)`.
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// Collects the remainder of the list whose [`SExprEvent::ListStart`] was just read, `name`
+  /// being the name it reported.
+  pub fn finish_list(&mut self, name: Option<&str>) -> Result<SExprList, String> {
+    let start = |name: Option<&str>| name.map(SExpr::symbol).into_iter().collect::<SExprList>();
+    let mut current = start(name);
+    let mut parents = vec![];
+
+    for event in self.by_ref() {
+      match event? {
+        SExprEvent::ListStart(name) => parents.push(std::mem::replace(&mut current, start(name))),
+        SExprEvent::Atom(atom) => current.0.push_back(atom),
+        SExprEvent::ListEnd => match parents.pop() {
+          Some(parent) => {
+            let child = std::mem::replace(&mut current, parent);
+            current.0.push_back(SExpr::List(child));
+          }
+          None => return Ok(current),
+        },
+      }
+    }
+
+    Err("Incomplete input".to_string())
+  }
+
+  /// Skips the remainder of the list whose [`SExprEvent::ListStart`] was just read.
+  pub fn skip_list(&mut self) -> Result<(), String> {
+    let mut depth = 1;
+    for event in self.by_ref() {
+      match event? {
+        SExprEvent::ListStart(_) => depth += 1,
+        SExprEvent::Atom(_) => {}
+        SExprEvent::ListEnd if depth == 1 => return Ok(()),
+        SExprEvent::ListEnd => depth -= 1,
+      }
+    }
+
+    Err("Incomplete input".to_string())
+  }
+
+  fn error(&mut self, message: &str) -> String {
+    self.failed = true;
+    format!("{message} at offset {}", self.input.len() - self.rest.len())
+  }
+
+  fn read_atom(&mut self) -> Result<SExprRef<'a>, String> {
+    let Ok((rest, atom)) = atom(self.rest) else {
+      return Err(self.error("Invalid atom"));
+    };
+
+    self.rest = rest;
+    if rest.starts_with(|c: char| !WHITESPACE.contains(&c) && c != '(' && c != ')') {
+      return Err(self.error("Expected separator"));
+    }
+    Ok(atom)
+  }
+}
+
+impl<'a> Iterator for SExprReader<'a> {
+  type Item = Result<SExprEvent<'a>, String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.failed {
+      return None;
+    }
+    if let Some(atom) = self.pending.take() {
+      return Some(Ok(SExprEvent::Atom(atom)));
+    }
+
+    self.rest = self.rest.trim_start_matches(WHITESPACE);
+    let Some(c) = self.rest.chars().next() else {
+      return match (self.depth, self.root_done) {
+        (0, true) => None,
+        (0, false) => Some(Err(self.error("Empty input"))),
+        _ => Some(Err(self.error("Incomplete input"))),
+      };
+    };
+
+    if self.depth == 0 && self.root_done {
+      return Some(Err(self.error("Unparsed input")));
+    }
+
+    match c {
+      '(' => {
+        self.rest = &self.rest[1..];
+        self.depth += 1;
+
+        let first = self.rest.trim_start_matches(WHITESPACE);
+        if first.is_empty() || first.starts_with(['(', ')']) {
+          return Some(Ok(SExprEvent::ListStart(None)));
+        }
+
+        Some(self.read_atom().map(|atom| match atom {
+          SExprRef::Symbol(name) => SExprEvent::ListStart(Some(name)),
+          atom => {
+            self.pending = Some(atom.into());
+            SExprEvent::ListStart(None)
+          }
+        }))
+      }
+      ')' if self.depth == 0 => Some(Err(self.error("Unbalanced ')'"))),
+      ')' => {
+        self.rest = &self.rest[1..];
+        self.depth -= 1;
+        self.root_done = self.depth == 0;
+        Some(Ok(SExprEvent::ListEnd))
+      }
+      _ if self.depth == 0 => Some(Err(self.error("Root must be list"))),
+      _ => Some(self.read_atom().map(|atom| SExprEvent::Atom(atom.into()))),
+    }
+  }
+}
@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+
+use nom::Offset;
+
+use super::{SExpr, SExprList, SExprSymbol, SExprValue, Span};
+
+/// Borrowed counterpart of [`SExpr`], with symbols and strings pointing into the parsed input
+#[derive(Debug, Clone, PartialEq)]
+pub enum SExprRef<'a> {
+  List(SExprListRef<'a>),
+  Symbol(&'a str),
+  /// Quoted string, borrowed unless it contained escape sequences
+  Value(Cow<'a, str>),
+  Int(i64),
+  Float(f64),
+  Hex(i64),
+  /// Content between `|` delimiters
+  Blob(&'a str),
+}
+
+impl<'a> SExprRef<'a> {
+  /// Returns the text of a symbol or string, `None` for lists and numbers.
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      SExprRef::Symbol(s) => Some(s),
+      SExprRef::Value(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  pub fn as_list(&self) -> Option<&SExprListRef<'a>> {
+    match self {
+      SExprRef::List(list) => Some(list),
+      _ => None,
+    }
+  }
+
+  /// Copies the expression into an owned [`SExpr`].
+  pub fn to_owned_sexpr(&self) -> SExpr {
+    match self {
+      SExprRef::List(list) => SExpr::List(list.to_owned_list()),
+      SExprRef::Symbol(s) => SExpr::Symbol(SExprSymbol(s.to_string())),
+      SExprRef::Value(s) => SExpr::Value(SExprValue(s.to_string())),
+      SExprRef::Int(i) => SExpr::Int(*i),
+      SExprRef::Float(f) => SExpr::Float(*f),
+      SExprRef::Hex(h) => SExpr::Hex(*h),
+      SExprRef::Blob(b) => SExpr::Blob(b.to_string()),
+    }
+  }
+}
+
+impl From<SExprRef<'_>> for SExpr {
+  fn from(value: SExprRef<'_>) -> Self {
+    value.to_owned_sexpr()
+  }
+}
+
+/// Borrowed counterpart of [`SExprList`], which also keeps the text it was parsed from
+#[derive(Clone, Default)]
+pub struct SExprListRef<'a>(pub Vec<SExprRef<'a>>, pub(super) &'a str);
+
+impl PartialEq for SExprListRef<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl std::fmt::Debug for SExprListRef<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_tuple("SExprListRef").field(&self.0).finish()
+  }
+}
+
+impl<'a> SExprListRef<'a> {
+  /// Returns the leading symbol of the list, e.g. `footprint` for `(footprint ...)`.
+  pub fn name(&self) -> Option<&'a str> {
+    match self.0.first()? {
+      SExprRef::Symbol(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, SExprRef<'a>> {
+    self.0.iter()
+  }
+
+  /// Iterates over the sub-lists named `name`.
+  pub fn lists_named<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s SExprListRef<'a>> {
+    self
+      .0
+      .iter()
+      .filter_map(SExprRef::as_list)
+      .filter(move |list| list.name() == Some(name))
+  }
+
+  /// Returns the text of the list, from the opening to the closing parenthesis.
+  pub fn source(&self) -> &'a str {
+    self.1
+  }
+
+  /// Locates the list in `input`, the text passed to [`super::parse_sexpr_borrowed`].
+  pub fn span_in(&self, input: &str) -> Span {
+    let start = input.offset(self.1);
+    Span {
+      start,
+      end: start + self.1.len(),
+    }
+  }
+
+  /// Copies the list into an owned [`SExprList`].
+  pub fn to_owned_list(&self) -> SExprList {
+    self.0.iter().map(SExprRef::to_owned_sexpr).collect()
+  }
+
+  /// Copies the list into an owned [`SExprList`], recording the span of every nested list in
+  /// `input`.
+  pub(super) fn to_owned_list_in(&self, input: &str) -> SExprList {
+    let items = self.0.iter().map(|expr| match expr {
+      SExprRef::List(list) => SExpr::List(list.to_owned_list_in(input)),
+      expr => expr.to_owned_sexpr(),
+    });
+
+    items.collect::<SExprList>().with_span(self.span_in(input))
+  }
+}
+
+impl From<SExprListRef<'_>> for SExprList {
+  fn from(value: SExprListRef<'_>) -> Self {
+    value.to_owned_list()
+  }
+}
@@ -0,0 +1,100 @@
+use std::fmt::{Display, Write};
+
+use super::{SExpr, SExprList};
+
+impl SExpr {
+  /// Appends the expression to `out` in KiCad's file formatting, with nested lists on their own
+  /// lines indented by `indent` tabs.
+  pub fn write(&self, out: &mut String, indent: usize) {
+    self.write_indented(out, indent, "\t");
+  }
+
+  fn write_indented(&self, out: &mut String, indent: usize, unit: &str) {
+    match self {
+      SExpr::List(list) => list.write_indented(out, indent, unit),
+      SExpr::Symbol(symbol) => out.push_str(&symbol.0),
+      SExpr::Value(value) => write_quoted(out, &value.0),
+      SExpr::Int(value) => {
+        let _ = write!(out, "{value}");
+      }
+      SExpr::Float(value) => {
+        let _ = write!(out, "{value}");
+      }
+      SExpr::Hex(value) => {
+        let _ = write!(out, "0x{value:08x}");
+      }
+      SExpr::Blob(blob) => {
+        let _ = write!(out, "|{blob}|");
+      }
+    }
+  }
+}
+
+impl SExprList {
+  /// Appends the list to `out`, see [`SExpr::write`].
+  ///
+  /// Lists made of atoms only are written on a single line. Otherwise the leading atoms stay on
+  /// the opening line and every following item goes on its own line.
+  pub fn write(&self, out: &mut String, indent: usize) {
+    self.write_indented(out, indent, "\t");
+  }
+
+  fn write_indented(&self, out: &mut String, indent: usize, unit: &str) {
+    out.push('(');
+
+    let mut multiline = false;
+    for (i, item) in self.0.iter().enumerate() {
+      multiline |= matches!(item, SExpr::List(_));
+      if multiline {
+        out.push('\n');
+        push_indent(out, indent + 1, unit);
+      } else if i > 0 {
+        out.push(' ');
+      }
+      item.write_indented(out, indent + 1, unit);
+    }
+
+    if multiline {
+      out.push('\n');
+      push_indent(out, indent, unit);
+    }
+    out.push(')');
+  }
+}
+
+/// Formats the expression like [`SExpr::write`], indenting nested lists by two spaces instead of
+/// tabs, for debugging output.
+impl Display for SExpr {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut out = String::new();
+    self.write_indented(&mut out, 0, "  ");
+    f.write_str(&out)
+  }
+}
+
+/// Formats the list like [`SExpr`]'s `Display`.
+impl Display for SExprList {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut out = String::new();
+    self.write_indented(&mut out, 0, "  ");
+    f.write_str(&out)
+  }
+}
+
+fn push_indent(out: &mut String, indent: usize, unit: &str) {
+  out.extend(std::iter::repeat_n(unit, indent));
+}
+
+/// Writes a quoted string, escaping quotes, backslashes and line breaks.
+fn write_quoted(out: &mut String, value: &str) {
+  out.push('"');
+  for c in value.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+}
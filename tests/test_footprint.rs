@@ -0,0 +1,857 @@
+use kicad_parser::{
+  common::*,
+  sexpr::{ToSExpr, parse_sexpr},
+};
+
+fn parse<T: TryFrom<kicad_parser::sexpr::SExpr, Error = kicad_parser::parser::ParserError>>(
+  input: &str,
+) -> T {
+  parse_sexpr(input).unwrap().as_sexpr_into().unwrap()
+}
+
+/// Writes `value` out as text and parses it back.
+fn round_trip<T>(value: &T) -> T
+where
+  T: ToSExpr + TryFrom<kicad_parser::sexpr::SExpr, Error = kicad_parser::parser::ParserError>,
+{
+  let mut out = String::new();
+  value.to_sexpr().write(&mut out, 0);
+  parse(&out)
+}
+
+#[test]
+pub fn pad_clearance_override() {
+  let pad: Pad =
+    parse(r#"(pad "1" smd rect (at 0 0) (size 1 1) (clearance 0.2) (zone_connect 2))"#);
+  assert_eq!(pad.clearance, Some(0.2));
+  assert_eq!(pad.zone_connection, Some(ZoneConnect::Solid));
+
+  let pad: Pad = parse(r#"(pad "1" smd rect (at 0 0) (size 1 1) (clearance 0) (zone_connect 0))"#);
+  assert_eq!(pad.clearance, Some(0.0));
+  assert_eq!(pad.zone_connection, Some(ZoneConnect::None));
+}
+
+#[test]
+pub fn footprint_clearance_override() {
+  let footprint: Footprint =
+    parse(r#"(footprint "Lib:Name" (layer "F.Cu") (clearance 0.2) (zone_connect 1))"#);
+  assert_eq!(footprint.clearance, Some(0.2));
+  assert_eq!(footprint.zone_connect, Some(ZoneConnect::Thermal));
+
+  let footprint: Footprint = parse(r#"(footprint "Lib:Name" (layer "F.Cu") (clearance 0))"#);
+  assert_eq!(footprint.clearance, Some(0.0));
+}
+
+#[test]
+pub fn library_link_split() {
+  let footprint: Footprint = parse(r#"(footprint "Resistor_SMD:R_0402" (layer "F.Cu"))"#);
+  assert_eq!(footprint.library_nickname(), Some("Resistor_SMD"));
+  assert_eq!(footprint.library_name(), Some("R_0402"));
+
+  let footprint: Footprint = parse(r#"(footprint "R_0402" (layer "F.Cu"))"#);
+  assert_eq!(footprint.library_nickname(), None);
+  assert_eq!(footprint.library_name(), Some("R_0402"));
+}
+
+#[test]
+pub fn distinct_pad_numbers() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+      (pad "1" smd rect (at 1 0) (size 1 1) (layers "F.Cu"))
+      (pad "2" smd rect (at 2 0) (size 1 1) (layers "F.Cu"))
+      (pad "" np_thru_hole circle (at 3 0) (size 1 1) (layers "*.Cu"))
+    )"#,
+  );
+  assert_eq!(footprint.distinct_pad_numbers(), 2);
+}
+
+#[test]
+pub fn layer_canonical_order() {
+  let mut layers: Vec<Layer> = [
+    "F.SilkS",
+    "Custom",
+    "B.Cu",
+    "Edge.Cuts",
+    "In2.Cu",
+    "F.Cu",
+    "B.Mask",
+    "In1.Cu",
+    "Another",
+  ]
+  .map(Layer::from)
+  .to_vec();
+  layers.sort();
+
+  let names: Vec<&str> = layers.iter().map(|l| l.layer_name.as_str()).collect();
+  assert_eq!(
+    names,
+    vec![
+      "F.Cu",
+      "In1.Cu",
+      "In2.Cu",
+      "B.Cu",
+      "F.SilkS",
+      "B.Mask",
+      "Edge.Cuts",
+      "Another",
+      "Custom"
+    ]
+  );
+}
+
+#[test]
+pub fn attributes_or_default() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Header" (layer "F.Cu")
+      (pad "1" thru_hole circle (at 0 0) (size 1.7 1.7) (drill 1) (layers "*.Cu"))
+      (pad "2" thru_hole circle (at 2.54 0) (size 1.7 1.7) (drill 1) (layers "*.Cu"))
+    )"#,
+  );
+  assert_eq!(footprint.attributes, None);
+  let attributes = footprint.attributes_or_default();
+  assert_eq!(attributes.footprint_type, FootprintType::ThroughHole);
+  assert!(!attributes.exclude_from_bom);
+
+  let footprint: Footprint =
+    parse(r#"(footprint "Lib:Name" (layer "F.Cu") (attr smd exclude_from_bom))"#);
+  let attributes = footprint.attributes_or_default();
+  assert_eq!(attributes.footprint_type, FootprintType::Smd);
+  assert!(attributes.exclude_from_bom);
+  assert!(!attributes.allow_soldermask_bridges);
+}
+
+#[test]
+pub fn modern_attributes() {
+  let attributes: FootprintAttributes = parse(
+    "(attr smd exclude_from_pos_files allow_soldermask_bridges allow_missing_courtyard (scale 2))",
+  );
+  assert!(attributes.exclude_from_pos_files);
+  assert!(attributes.allow_soldermask_bridges);
+  assert!(attributes.allow_missing_courtyard);
+  assert_eq!(attributes.scale, Some(2.0));
+
+  assert_eq!(round_trip(&attributes), attributes);
+}
+
+#[test]
+pub fn coincident_pads() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+      (pad "2" smd rect (at 2 0) (size 1 1) (layers "F.Cu"))
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+      (pad "2" smd rect (at 2 0) (size 2 2) (layers "F.Cu"))
+    )"#,
+  );
+  assert_eq!(footprint.coincident_pads(), vec![(0, 2)]);
+}
+
+#[test]
+pub fn canonical_layers() {
+  assert_eq!(Layer::from("F.Cu").canonical(), Some(CanonicalLayer::FCu));
+  assert_eq!(
+    Layer::from("In30.Cu").canonical(),
+    Some(CanonicalLayer::In30Cu)
+  );
+  assert_eq!(
+    Layer::from("F.Silkscreen").canonical(),
+    Some(CanonicalLayer::FSilkS)
+  );
+  assert_eq!(Layer::from("F.Cuu").canonical(), None);
+  assert_eq!(Layer::from("My.Layer").canonical(), None);
+
+  assert_eq!(
+    "Edge.Cuts".parse::<CanonicalLayer>().unwrap(),
+    CanonicalLayer::EdgeCuts
+  );
+  assert!("In31.Cu".parse::<CanonicalLayer>().is_err());
+  assert_eq!(CanonicalLayer::BCrtYd.to_string(), "B.CrtYd");
+  assert!(CanonicalLayer::In5Cu.is_copper() && !CanonicalLayer::FMask.is_copper());
+
+  for layer in CanonicalLayer::ALL {
+    assert_eq!(layer.to_string().parse::<CanonicalLayer>().unwrap(), *layer);
+    assert_eq!(
+      Layer::from(layer.name()).canonical_rank(),
+      Some(layer.rank())
+    );
+  }
+  assert_eq!(CanonicalLayer::BCu.rank(), 31);
+}
+
+#[test]
+pub fn graphics_on_layer() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (fp_line (start 0 0) (end 1 0) (stroke (width 0.12) (type solid)) (layer "F.SilkS"))
+      (fp_line (start 0 1) (end 1 1) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+      (fp_circle (center 0 0) (end 1 0) (stroke (width 0.12) (type solid)) (fill no) (layer "F.SilkS"))
+      (fp_line (start 0 2) (end 1 2) (stroke (width 0.1) (type solid)) (layer "F.Fab"))
+    )"#,
+  );
+  assert_eq!(footprint.graphics.len(), 4);
+  assert_eq!(footprint.graphics_on_layer("F.SilkS").count(), 2);
+  assert_eq!(footprint.graphics_on_layer("F.CrtYd").count(), 1);
+  assert_eq!(footprint.graphics_on_layer("B.SilkS").count(), 0);
+}
+
+#[test]
+pub fn bare_symbol_layers() {
+  let layer: Layer = parse("(layer F.Cu)");
+  assert_eq!(layer, *"F.Cu");
+
+  let layer: Layer = parse(r#"(layer "B.SilkS" knockout)"#);
+  assert_eq!(layer, *"B.SilkS");
+  assert!(layer.knockout);
+
+  let pad: Pad = parse("(pad 1 thru_hole circle (at 0 0) (size 1.7 1.7) (layers *.Cu *.Mask))");
+  assert_eq!(pad.layers, vec![Layer::from("*.Cu"), Layer::from("*.Mask")]);
+}
+
+#[test]
+pub fn pad_drill() {
+  let pad: Pad = parse(
+    r#"(pad "1" thru_hole circle (at 0 0) (size 1.7 1.7) (drill 1) (layers "*.Cu" "*.Mask"))"#,
+  );
+  let drill = pad.drill.unwrap();
+  assert!(!drill.oval);
+  assert_eq!(drill.diameter, 1.0);
+  assert_eq!(drill.width, None);
+  assert_eq!(drill.offset, None);
+
+  let pad: Pad = parse(
+    r#"(pad "2" thru_hole oval (at 2.54 0) (size 1.7 2.4) (drill oval 0.8 1.5 (offset 0 0.2)) (layers "*.Cu"))"#,
+  );
+  let drill = pad.drill.unwrap();
+  assert!(drill.oval);
+  assert_eq!(drill.diameter, 0.8);
+  assert_eq!(drill.width, Some(1.5));
+  assert_eq!(drill.offset, Some(Point::new(0.0, 0.2)));
+
+  // A zero diameter is still the first dimension
+  let drill: Drill = parse("(drill oval 0 1.5)");
+  assert_eq!((drill.diameter, drill.width), (0.0, Some(1.5)));
+  assert_eq!(round_trip(&drill), drill);
+  let drill: Drill = parse("(drill 0)");
+  assert_eq!((drill.diameter, drill.width), (0.0, None));
+
+  let pad: Pad = parse(r#"(pad "3" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))"#);
+  assert_eq!(pad.drill, None);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+pub fn clone_at() {
+  use std::collections::HashSet;
+
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu") (uuid "5e1c6a2e-0000-0000-0000-000000000000") (at 1 2)
+      (property "Reference" "R1" (at 0 -1 0) (layer "F.SilkS") (uuid "r"))
+      (fp_line (start 0 0) (end 1 0) (stroke (width 0.12) (type solid)) (layer "F.SilkS") (uuid "l"))
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu") (uuid "p"))
+      (zone (net 0) (net_name "") (layer "F.Cu") (uuid "z")
+        (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1))))
+      (group "" (uuid "g") (members "l" "p"))
+    )"#,
+  );
+
+  let copies: Vec<Footprint> = (0..3)
+    .map(|i| {
+      footprint.clone_at(Position {
+        x: 10.0 * i as f64,
+        y: 5.0,
+        angle: Some(90.0),
+      })
+    })
+    .collect();
+
+  let mut uuids = HashSet::new();
+  uuids.insert(footprint.uuid.clone().unwrap());
+  for (i, copy) in copies.iter().enumerate() {
+    let position = copy.position.as_ref().unwrap();
+    assert_eq!((position.x, position.y), (10.0 * i as f64, 5.0));
+    assert_eq!(position.angle, Some(90.0));
+    assert_eq!(copy.pads.len(), 1);
+
+    assert!(uuids.insert(copy.uuid.clone().unwrap()));
+    assert!(uuids.insert(copy.pads[0].uuid.clone()));
+    assert!(uuids.insert(copy.graphics[0].uuid().clone()));
+
+    let all = all_uuids(copy);
+    assert_eq!(all.len(), 6);
+    assert!(all.is_disjoint(&all_uuids(&footprint)));
+    assert_eq!(
+      copy.groups[0].members,
+      vec![copy.graphics[0].uuid().clone(), copy.pads[0].uuid.clone()]
+    );
+  }
+  assert_eq!(uuids.len(), 10);
+}
+
+#[cfg(feature = "uuid")]
+fn all_uuids(footprint: &Footprint) -> std::collections::HashSet<Uuid> {
+  let mut uuids: std::collections::HashSet<Uuid> = footprint.uuid.iter().cloned().collect();
+  uuids.extend(footprint.pads.iter().map(|pad| pad.uuid.clone()));
+  uuids.extend(
+    footprint
+      .graphics
+      .iter()
+      .map(|graphic| graphic.uuid().clone()),
+  );
+  uuids.extend(
+    footprint
+      .properties
+      .iter()
+      .filter_map(|property| property.uuid.clone()),
+  );
+  uuids.extend(footprint.zones.iter().map(|zone| zone.uuid.clone()));
+  uuids.extend(footprint.groups.iter().map(|group| group.uuid.clone()));
+  uuids
+}
+
+#[test]
+pub fn model_resolved_path() {
+  use std::{collections::HashMap, path::PathBuf};
+
+  let model = Model3D {
+    file: "${KICAD7_3DMODEL_DIR}/Resistor_SMD.3dshapes/R_0402.wrl".to_string(),
+    position: (0.0, 0.0, 0.0),
+    scale: (1.0, 1.0, 1.0),
+    rotation: (0.0, 0.0, 0.0),
+  };
+
+  let vars = HashMap::from([(
+    "KICAD7_3DMODEL_DIR".to_string(),
+    "/usr/share/kicad/3dmodels".to_string(),
+  )]);
+  assert_eq!(
+    model.resolved_path(&vars),
+    PathBuf::from("/usr/share/kicad/3dmodels/Resistor_SMD.3dshapes/R_0402.wrl")
+  );
+  assert_eq!(
+    model.resolved_path(&HashMap::new()),
+    PathBuf::from("${KICAD7_3DMODEL_DIR}/Resistor_SMD.3dshapes/R_0402.wrl")
+  );
+}
+
+#[test]
+pub fn paste_area() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (pad "1" smd rect (at -1 0) (size 1 2) (layers "F.Cu" "F.Paste" "F.Mask"))
+      (pad "2" smd rect (at 1 0) (size 1 2) (layers "F.Cu" "F.Paste" "F.Mask"))
+      (pad "3" smd rect (at 0 2) (size 1 1) (layers "F.Cu" "F.Mask"))
+      (pad "" np_thru_hole circle (at 0 -2) (size 1 1) (drill 1) (layers "*.Cu" "*.Mask"))
+    )"#,
+  );
+  assert!((footprint.paste_area() - 4.0).abs() < 1e-9);
+
+  // A 0.1 mm paste reduction on each side
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu") (solder_paste_margin -0.1)
+      (pad "1" smd rect (at -1 0) (size 1 2) (layers "F.Cu" "F.Paste"))
+      (pad "2" smd rect (at 1 0) (size 1 2) (layers "F.Cu" "F.Paste") (solder_paste_margin 0))
+    )"#,
+  );
+  assert!((footprint.paste_area() - (0.8 * 1.8 + 2.0)).abs() < 1e-9);
+
+  // A -10% ratio shrinks each side by a tenth of the pad size along that axis
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (pad "1" smd rect (at 0 0) (size 1 2) (layers "F.Cu" "F.Paste")
+        (solder_paste_margin_ratio -0.1))
+    )"#,
+  );
+  assert!((footprint.paste_area() - 0.8 * 1.6).abs() < 1e-9);
+}
+
+#[test]
+pub fn chamfered_pad() {
+  let pad: Pad = parse(
+    r#"(pad "1" smd roundrect (at 0 0) (size 1.5 1) (layers "F.Cu" "F.Paste" "F.Mask")
+      (roundrect_rratio 0.25) (chamfer_ratio 0.2) (chamfer top_left bottom_right)
+      (thermal_bridge_angle 45))"#,
+  );
+  assert_eq!(pad.shape, PadShape::RoundedRectangle);
+  assert_eq!(pad.roundrect_rratio, Some(0.25));
+  assert_eq!(pad.chamfer_ratio, Some(0.2));
+  assert_eq!(
+    pad.chamfer,
+    vec![PadCorner::TopLeft, PadCorner::BottomRight]
+  );
+  assert_eq!(pad.thermal_bridge_angle, Some(45.0));
+
+  let footprint: Footprint =
+    parse(r#"(footprint "Lib:Name" (layer "F.Cu") (thermal_bridge_width 0.3) (thermal_gap 0.4))"#);
+  assert_eq!(footprint.thermal_width, Some(0.3));
+  assert_eq!(footprint.thermal_gap, Some(0.4));
+}
+
+#[test]
+pub fn pad_without_shape() {
+  let pad: Pad = parse(r#"(pad "1" smd (at 0 0) (size 1 1) (layers "F.Cu"))"#);
+  assert_eq!(pad.shape, PadShape::Rectangle);
+  assert!(!pad.has_explicit_shape());
+
+  // Written back with its default shape, the pad is still equal
+  let written = round_trip(&pad);
+  assert!(written.has_explicit_shape());
+  assert_eq!(written, pad);
+
+  let pad: Pad = parse(r#"(pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))"#);
+  assert!(pad.has_explicit_shape());
+
+  let built = Pad {
+    number: "1".to_string(),
+    shape: PadShape::Circle,
+    explicit_shape: true,
+    ..Default::default()
+  };
+  assert!(built.has_explicit_shape());
+}
+
+#[test]
+pub fn smd_or_tht() {
+  // Declared by the attributes, even when the pads disagree
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu") (attr smd)
+      (pad "1" thru_hole circle (at 0 0) (size 1 1) (drill 0.5) (layers "*.Cu")))"#,
+  );
+  assert!(footprint.is_smd());
+  assert!(!footprint.is_tht());
+
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu") (attr through_hole)
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu")))"#,
+  );
+  assert!(footprint.is_tht());
+
+  // Inferred from the pads, where any through-hole pad wins
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+      (pad "2" thru_hole circle (at 2 0) (size 1 1) (drill 0.5) (layers "*.Cu")))"#,
+  );
+  assert!(footprint.is_tht());
+  assert!(!footprint.is_smd());
+
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu") (attr board_only)
+      (pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu")))"#,
+  );
+  assert!(footprint.is_smd());
+
+  let footprint: Footprint = parse(r#"(footprint "Lib:Name" (layer "F.Cu"))"#);
+  assert!(!footprint.is_smd());
+  assert!(!footprint.is_tht());
+}
+
+#[test]
+pub fn layer_construction() {
+  let layer = Layer::new("F.Cu");
+  assert_eq!(layer.as_str(), "F.Cu");
+  assert!(!layer.knockout);
+  assert_eq!(Layer::from("F.Cu"), layer);
+  assert!(layer == "F.Cu");
+  assert!(layer != "B.Cu");
+
+  let pad: Pad = parse(r#"(pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu" "F.Mask"))"#);
+  assert!(pad.layers.iter().any(|l| l == "F.Mask"));
+  assert!(!pad.layers.iter().any(|l| l == "B.Cu"));
+}
+
+#[test]
+pub fn models() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (model "${KICAD8_3DMODEL_DIR}/Resistor_SMD.3dshapes/R_0402.wrl"
+        (offset (xyz 0.5 -1 0)) (scale (xyz 1 1 2)) (rotate (xyz 0 0 90)))
+      (model "legacy.wrl" (at (xyz 0.1 0 0)) (scale (xyz 1 1 1)) (rotate (xyz 0 0 0))))"#,
+  );
+  assert_eq!(footprint.models.len(), 2);
+
+  let model = &footprint.models[0];
+  assert_eq!(
+    model.file,
+    "${KICAD8_3DMODEL_DIR}/Resistor_SMD.3dshapes/R_0402.wrl"
+  );
+  assert_eq!(model.position, (0.5, -1.0, 0.0));
+  assert_eq!(model.scale, (1.0, 1.0, 2.0));
+  assert_eq!(model.rotation, (0.0, 0.0, 90.0));
+
+  // The legacy offset is given in inches
+  let model = &footprint.models[1];
+  assert!((model.position.0 - 2.54).abs() < 1e-9);
+
+  let model: Model3D = parse(r#"(model "part.step")"#);
+  assert_eq!(model.scale, (1.0, 1.0, 1.0));
+}
+
+#[test]
+pub fn footprint_zone() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Lib:Name" (layer "F.Cu")
+      (zone (net 0) (net_name "") (layer "F.Cu") (name "no copper") (hatch full 0.508)
+        (connect_pads yes (clearance 0))
+        (polygon (pts (xy -1 -1) (xy 1 -1) (xy 1 1) (xy -1 1)))))"#,
+  );
+  assert_eq!(footprint.zones.len(), 1);
+
+  let zone = &footprint.zones[0];
+  assert_eq!(zone.layers, vec![Layer::new("F.Cu")]);
+  assert_eq!(zone.name.as_deref(), Some("no copper"));
+  assert_eq!(zone.hatch.as_ref().unwrap().style, ZoneHatchStyle::Full);
+  assert_eq!(
+    zone.connect_pads.as_ref().unwrap().connection,
+    Some(ZonePadConnection::Solid)
+  );
+  assert_eq!(zone.polygon.0.len(), 4);
+  assert!(zone.filled_polygons.is_empty());
+}
+
+#[test]
+pub fn placed_properties() {
+  let footprint: Footprint = parse(
+    r#"(footprint "R_0402" (layer "F.Cu")
+      (property "Reference" "R1" (at 0 -1.2 90) (layer "F.SilkS") (uuid "ref-uuid")
+        (effects (font (size 1 1) (thickness 0.15))))
+      (property "Value" "10k" (at 0 1.2 0) (layer "F.Fab") (hide yes)
+        (effects (font (size 1 1) (thickness 0.15))))
+      (property ki_fp_filters "R_*"))"#,
+  );
+
+  assert_eq!(footprint.properties.len(), 3);
+  assert_eq!(footprint.reference(), Some("R1"));
+  assert_eq!(footprint.property("ki_fp_filters"), Some("R_*"));
+  assert_eq!(footprint.property("Datasheet"), None);
+
+  let reference = &footprint.properties[0];
+  assert_eq!(reference.position.as_ref().unwrap().angle, Some(90.0));
+  assert_eq!(reference.layer.as_ref().unwrap(), "F.SilkS");
+  assert_eq!(reference.uuid, Some(Uuid("ref-uuid".to_string())));
+  assert_eq!(reference.effects.as_ref().unwrap().font.size, (1.0, 1.0));
+  assert!(!reference.hide);
+  assert!(footprint.properties[1].hide);
+
+  let bare = &footprint.properties[2];
+  assert!(bare.position.is_none() && bare.layer.is_none() && bare.effects.is_none());
+
+  assert_eq!(round_trip(&footprint), footprint);
+}
+
+#[test]
+pub fn net_tie_and_private_layers() {
+  let footprint: Footprint = parse(
+    r#"(footprint "NetTie-2" (layer "F.Cu")
+      (private_layers "F.SilkS" "B.SilkS")
+      (net_tie_pad_groups "1,2" "3, 4, 5"))"#,
+  );
+
+  assert_eq!(
+    footprint.private_layers,
+    vec![Layer::from("F.SilkS"), Layer::from("B.SilkS")]
+  );
+  assert_eq!(
+    footprint.net_tie_pad_groups,
+    vec![vec!["1", "2"], vec!["3", "4", "5"]]
+  );
+
+  assert_eq!(round_trip(&footprint), footprint);
+}
+
+#[test]
+pub fn footprint_embedded_files() {
+  let footprint: Footprint = parse(
+    r#"(footprint "Logo" (layer "F.Cu")
+      (embedded_fonts yes)
+      (embedded_files
+        (file (name "logo.step") (type model) (data |SVNPLTEwMzAzLTIx|) (checksum "A1B2")))
+      (model "kicad-embed://logo.step"))"#,
+  );
+
+  assert_eq!(footprint.embedded_fonts, Some(true));
+  let [file] = footprint.embedded_files.as_slice() else {
+    panic!(
+      "expected one embedded file, got {:?}",
+      footprint.embedded_files
+    );
+  };
+  assert_eq!(file.name, "logo.step");
+  assert_eq!(file.file_type, EmbeddedFileType::Model);
+  assert_eq!(file.data, b"ISO-10303-21");
+  assert_eq!(round_trip(&footprint), footprint);
+}
+
+#[test]
+pub fn footprint_file() {
+  let footprint = parse_footprint_file(
+    r#"(footprint "R_0402_1005Metric"
+  (version 20240108)
+  (generator "pcbnew")
+  (generator_version "8.0")
+  (layer "F.Cu")
+  (descr "Resistor SMD 0402 (1005 Metric)")
+  (tags "resistor")
+  (property "Reference" "REF**" (at 0 -1.17 0) (layer "F.SilkS")
+    (effects (font (size 1 1) (thickness 0.15))))
+  (property "Value" "R_0402_1005Metric" (at 0 1.17 0) (layer "F.Fab")
+    (effects (font (size 1 1) (thickness 0.15))))
+  (attr smd)
+  (fp_line (start -0.93 0.47) (end -0.93 -0.47) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+  (pad "1" smd roundrect (at -0.51 0) (size 0.54 0.64) (layers "F.Cu" "F.Mask" "F.Paste")
+    (roundrect_rratio 0.25))
+  (pad "2" smd roundrect (at 0.51 0) (size 0.54 0.64) (layers "F.Cu" "F.Mask" "F.Paste")
+    (roundrect_rratio 0.25))
+)
+"#,
+  )
+  .unwrap();
+
+  assert_eq!(footprint.library_link.as_deref(), Some("R_0402_1005Metric"));
+  assert_eq!(footprint.version.as_deref(), Some("20240108"));
+  assert_eq!(footprint.generator.as_deref(), Some("pcbnew"));
+  assert_eq!(footprint.generator_version.as_deref(), Some("8.0"));
+  assert_eq!(
+    footprint.description.as_deref(),
+    Some("Resistor SMD 0402 (1005 Metric)")
+  );
+  assert_eq!(footprint.reference(), Some("REF**"));
+  assert_eq!(footprint.pads.len(), 2);
+  assert_eq!(footprint.graphics.len(), 1);
+
+  let mut out = String::new();
+  footprint.to_sexpr().write(&mut out, 0);
+  assert!(out.contains("(version 20240108)"), "{out}");
+  assert_eq!(parse_footprint_file(&out).unwrap(), footprint);
+
+  let error = parse_footprint_file(r#"(kicad_pcb (version 20240108))"#).unwrap_err();
+  assert_eq!(
+    error.line_column(r#"(kicad_pcb (version 20240108))"#),
+    Some((1, 1))
+  );
+  assert!(parse_footprint_file("(footprint").is_err());
+}
+
+#[test]
+pub fn rotated_bounding_box() {
+  let footprint = |angle: &str| -> Footprint {
+    parse(&format!(
+      r#"(footprint "R" (layer "F.Cu") (at 10 10 {angle})
+        (fp_rect (start -1 -0.5) (end 1 0.5) (stroke (width 0) (type solid)) (layer "F.CrtYd"))
+        (fp_line (start 2 0) (end 3 0) (stroke (width 0) (type solid)) (layer "F.SilkS")))"#
+    ))
+  };
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+  let bbox = footprint("0").bounding_box();
+  assert!(
+    close(bbox.min_x, 9.0) && close(bbox.max_x, 13.0),
+    "{bbox:?}"
+  );
+  assert!(
+    close(bbox.min_y, 9.5) && close(bbox.max_y, 10.5),
+    "{bbox:?}"
+  );
+
+  let rotated = footprint("90");
+  let bbox = rotated.bounding_box();
+  assert!(
+    close(bbox.min_x, 9.5) && close(bbox.max_x, 10.5),
+    "{bbox:?}"
+  );
+  assert!(
+    close(bbox.min_y, 7.0) && close(bbox.max_y, 11.0),
+    "{bbox:?}"
+  );
+
+  let Graphic::Line(line) = &rotated.graphics_in_board_space()[1] else {
+    panic!("expected a line");
+  };
+  assert!(
+    close(line.start.x, 10.0) && close(line.start.y, 8.0),
+    "{line:?}"
+  );
+
+  // A rectangle turned by 45° is no longer axis aligned
+  let turned = footprint("45").graphics_in_board_space();
+  assert!(matches!(&turned[0], Graphic::Polygon(poly) if poly.points.0.len() == 4));
+}
+
+#[test]
+pub fn rotated_pad_bounding_box() {
+  let footprint: Footprint = parse(
+    r#"(footprint "R" (layer "F.Cu") (at 10 20 90)
+      (pad "1" smd rect (at 1 0 90) (size 1 0.5) (layers "F.Cu" "F.Paste"))
+      (pad "2" smd rect (at -1 0 90) (size 1 0.5) (layers "B.Cu")))"#,
+  );
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+  let bbox = footprint.pad_bounding_box(&Layer::from("F.Cu"));
+  assert!(
+    close(bbox.min_x, 9.75) && close(bbox.max_x, 10.25),
+    "{bbox:?}"
+  );
+  assert!(
+    close(bbox.min_y, 18.5) && close(bbox.max_y, 19.5),
+    "{bbox:?}"
+  );
+  assert!(
+    footprint
+      .pad_bounding_box(&Layer::from("B.Paste"))
+      .is_empty()
+  );
+}
+
+#[test]
+pub fn rotated_custom_pad_bounding_box() {
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+  let custom = |angle: &str| -> Pad {
+    parse(&format!(
+      r#"(pad "1" smd custom (at 10 10 {angle}) (size 0.5 0.5) (layers "F.Cu")
+        (options (clearance outline) (anchor rect))
+        (primitives
+          (gr_poly (pts (xy 0 0) (arc (start 1 0) (mid 2 1) (end 1 2)) (xy 0 2)) (width 0) (fill yes))))"#
+    ))
+  };
+
+  // Unrotated, the arc bulges out to x = 2 and the outline keeps its orientation
+  let bbox = custom("0").bounding_box();
+  assert!(
+    close(bbox.min_x, 10.0) && close(bbox.max_x, 12.0),
+    "{bbox:?}"
+  );
+  assert!(
+    close(bbox.min_y, 10.0) && close(bbox.max_y, 12.0),
+    "{bbox:?}"
+  );
+
+  // Turned by 90° the arc ends up above the anchor, like the pad's own outline would
+  let bbox = custom("90").bounding_box();
+  assert!(
+    close(bbox.min_x, 10.0) && close(bbox.max_x, 12.0),
+    "{bbox:?}"
+  );
+  assert!(
+    close(bbox.min_y, 8.0) && close(bbox.max_y, 10.0),
+    "{bbox:?}"
+  );
+}
+
+#[test]
+pub fn pad_pin_mapping() {
+  let pad: Pad = parse(
+    r#"(pad "3" smd rect (at 0 0) (size 1 1) (layers "F.Cu")
+      (net 4 "/CLK") (pinfunction "CLK") (pintype "input") (die_length 0.5))"#,
+  );
+  assert_eq!(pad.pin_function.as_deref(), Some("CLK"));
+  assert_eq!(pad.pin_type.as_deref(), Some("input"));
+  assert_eq!(pad.die_length, Some(0.5));
+
+  assert_eq!(round_trip(&pad), pad);
+}
+
+#[test]
+pub fn pad_outline() {
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+  let extent = |pad: &Pad| {
+    let bbox = pad.bounding_box();
+    (bbox.min_x, bbox.min_y, bbox.max_x, bbox.max_y)
+  };
+
+  // Circles ignore the second size and rotation
+  let circle: Pad = parse(r#"(pad "1" thru_hole circle (at 1 2 30) (size 2 3) (layers "*.Cu"))"#);
+  assert_eq!(extent(&circle), (0.0, 1.0, 2.0, 3.0));
+  let outline = circle.outline_points();
+  assert_eq!(outline.len(), 32);
+  assert!(
+    outline
+      .iter()
+      .all(|point| close((point.x - 1.0).hypot(point.y - 2.0), 1.0))
+  );
+
+  // A rectangle turned by 90° swaps its sides
+  let rect: Pad = parse(r#"(pad "1" smd rect (at 0 0 90) (size 4 2) (layers "F.Cu"))"#);
+  let (min_x, min_y, max_x, max_y) = extent(&rect);
+  assert!(close(min_x, -1.0) && close(max_x, 1.0) && close(min_y, -2.0) && close(max_y, 2.0));
+  assert_eq!(rect.outline_points().len(), 4);
+
+  // An oval at 45° is narrower than its rotated rectangle
+  let oval: Pad = parse(r#"(pad "1" smd oval (at 0 0 45) (size 4 2) (layers "F.Cu"))"#);
+  let (min_x, .., max_x, _) = extent(&oval);
+  let half_width = 1.0 / 2f64.sqrt() + 1.0;
+  assert!(close(min_x, -half_width) && close(max_x, half_width));
+  let outline = BoundingBox::from_points(oval.outline_points());
+  assert!(outline.min_x >= min_x - 1e-9 && outline.max_x <= max_x + 1e-9);
+  assert!(close(outline.width(), 2.0 * half_width));
+
+  let rounded: Pad =
+    parse(r#"(pad "1" smd roundrect (at 0 0) (size 2 1) (layers "F.Cu") (roundrect_rratio 0.25))"#);
+  assert_eq!(extent(&rounded), (-1.0, -0.5, 1.0, 0.5));
+  assert_eq!(rounded.outline_points().len(), 36);
+}
+
+#[test]
+pub fn legacy_module() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+
+  let footprint = parse_footprint_file(
+    r#"(module Resistor_SMD:R_0805_2012Metric (layer F.Cu) (tedit 5F68FEEE)
+  (attr smd)
+  (pad 1 smd roundrect (at -0.9 0) (size 1 1.4) (layers F.Cu F.Paste F.Mask) (roundrect_rratio 0.25))
+)"#,
+  )
+  .unwrap();
+  assert_eq!(
+    footprint.library_link.as_deref(),
+    Some("Resistor_SMD:R_0805_2012Metric")
+  );
+  assert_eq!(footprint.tedit.as_deref(), Some("5F68FEEE"));
+  assert_eq!(footprint.pads.len(), 1);
+
+  let footprint: Footprint = parse(r#"(module "Lib:Name" locked (layer "F.Cu"))"#);
+  assert_eq!(footprint.library_link.as_deref(), Some("Lib:Name"));
+  assert!(footprint.locked);
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20171130)
+  (module Lib:Name (layer F.Cu) (at 10 20))
+  (footprint "Lib:Other" (layer "F.Cu"))
+)"#,
+  )
+  .unwrap();
+  assert_eq!(pcb.footprints.len(), 2);
+  assert_eq!(pcb.footprints[0].library_link.as_deref(), Some("Lib:Name"));
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+pub fn typed_uuid() {
+  use kicad_parser::{parser::ParseOptions, pcb_file::parse_pcb_file_with};
+
+  let uuid: Uuid = parse(r#"(uuid "5E1C6A2E-1234-4abc-8def-000000000001")"#);
+  let lower: Uuid = parse(r#"(uuid "5e1c6a2e-1234-4abc-8def-000000000001")"#);
+  assert_ne!(uuid, lower);
+  assert_eq!(uuid.as_uuid(), lower.as_uuid());
+  assert!(uuid.as_uuid().is_some());
+
+  let zero: Uuid = parse(r#"(uuid "00000000-0000-0000-0000-00005f68feee")"#);
+  assert!(zero.as_uuid().is_some());
+
+  let malformed: Uuid = parse(r#"(uuid "not-a-uuid")"#);
+  assert_eq!(malformed.as_uuid(), None);
+
+  let board = |uuid: &str| {
+    format!(
+      r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (gr_line (start 0 0) (end 1 0) (stroke (width 0.1) (type solid)) (layer "Edge.Cuts") (uuid "{uuid}"))
+)"#
+    )
+  };
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  assert!(parse_pcb_file_with(&board("5e1c6a2e-1234-4abc-8def-000000000001"), strict).is_ok());
+  assert!(parse_pcb_file_with(&board("not-a-uuid"), strict).is_err());
+  assert!(parse_pcb_file_with(&board("not-a-uuid"), ParseOptions::default()).is_ok());
+}
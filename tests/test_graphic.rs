@@ -0,0 +1,399 @@
+use kicad_parser::{common::*, sexpr::parse_sexpr};
+
+fn parse<T: TryFrom<kicad_parser::sexpr::SExpr, Error = kicad_parser::parser::ParserError>>(
+  input: &str,
+) -> T {
+  parse_sexpr(input).unwrap().as_sexpr_into().unwrap()
+}
+
+#[test]
+pub fn text_lines() {
+  let text: FootprintText =
+    parse(r#"(fp_text user "first\nsecond" (at 0 0) (layer "F.SilkS") (uuid "a"))"#);
+  assert_eq!(text.lines(), vec!["first", "second"]);
+
+  let text: FootprintText = parse(r#"(fp_text user "single" (at 0 0) (layer "F.SilkS"))"#);
+  assert_eq!(text.lines(), vec!["single"]);
+}
+
+#[test]
+pub fn hatched_zone_fill() {
+  use kicad_parser::{
+    parser::ParseOptions,
+    pcb_file::{parse_pcb_file_with, write_pcb_file},
+  };
+
+  let board = r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (zone (net 1) (net_name "GND") (layer "B.Cu") (uuid "6f1e2d3c-4b5a-4968-8776-5a4b3c2d1e0f") (hatch edge 0.5)
+    (connect_pads (clearance 0.5))
+    (min_thickness 0.25)
+    (fill yes (mode hatch) (thermal_gap 0.5) (thermal_bridge_width 0.5) (smoothing chamfer) (radius 0.5)
+      (island_removal_mode 1) (hatch_thickness 1) (hatch_gap 1.5) (hatch_orientation 45)
+      (hatch_smoothing_level 0) (hatch_smoothing_value 0.1) (hatch_border_algorithm hatch_thickness)
+      (hatch_min_hole_area 0.3))
+    (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10)))
+  )
+)"#;
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  let pcb = parse_pcb_file_with(board, strict).unwrap();
+
+  let zone = &pcb.zones[0];
+  assert_eq!(
+    zone.fill,
+    Some(FillType::Hatch {
+      spacing: Some(1.5),
+      thickness: Some(1.0),
+    })
+  );
+  assert_eq!(zone.fill_settings.thermal_gap, Some(0.5));
+  assert_eq!(zone.fill_settings.hatch_orientation, Some(45.0));
+  assert_eq!(
+    zone.fill_settings.hatch_border_algorithm.as_deref(),
+    Some("hatch_thickness")
+  );
+  assert_eq!(
+    parse_pcb_file_with(&write_pcb_file(&pcb), strict).unwrap(),
+    pcb
+  );
+
+  let zone: Zone = parse(
+    r#"(zone (net 0) (net_name "") (layer "F.Cu")
+      (fill yes (thermal_gap 0.5) (thermal_bridge_width 0.5))
+      (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1))))"#,
+  );
+  assert_eq!(zone.fill, Some(FillType::Solid));
+}
+
+#[test]
+pub fn text_effects() {
+  let text: FootprintText = parse(
+    r#"(fp_text reference "R1" (at 0 -1.5) (layer "F.SilkS")
+      (effects (font (size 1.2 1) (thickness 0.15) bold italic (face "Arial")) (justify mirror right top))
+    )"#,
+  );
+  let effects = &text.effects;
+  assert_eq!(effects.font.size, (1.2, 1.0));
+  assert_eq!(effects.font.thickness, 0.15);
+  assert!(effects.font.bold && effects.font.italic);
+  assert_eq!(effects.font.face.as_deref(), Some("Arial"));
+  assert!(effects.mirror);
+  let justify = effects.justify.as_ref().unwrap();
+  assert_eq!(justify.horizontal, Some(HorizontalJustify::Right));
+  assert_eq!(justify.vertical, Some(VerticalJustify::Top));
+  assert!(!text.hide);
+
+  let text: FootprintText = parse(
+    r#"(fp_text value "10k" (at 0 1.5) (layer "F.Fab") (effects (font (size 1 1) (bold yes)) hide))"#,
+  );
+  assert!(text.effects.font.bold);
+  assert_eq!(text.effects.justify, None);
+  assert!(text.hide);
+
+  let text: FootprintTextBox = parse(
+    r#"(fp_text_box "note" (start 0 0) (end 5 2) (layer "F.Fab") (effects (font (size 0.8 0.8)) (justify left)))"#,
+  );
+  assert_eq!(text.effects.font.size, (0.8, 0.8));
+  assert_eq!(
+    text.effects.justify.unwrap().horizontal,
+    Some(HorizontalJustify::Left)
+  );
+}
+
+#[test]
+pub fn unknown_stroke_type() {
+  use kicad_parser::{parser::ParseOptions, pcb_file::parse_pcb_file_with_warnings};
+
+  let line: FootprintLine =
+    parse(r#"(fp_line (start 0 0) (end 1 0) (stroke (width 0.1) (type wavy)) (layer "F.SilkS"))"#);
+  assert_eq!(line.stroke.line_type, StrokeType::Default);
+  assert_eq!(line.stroke.width, 0.1);
+
+  let line: FootprintLine = parse(
+    r#"(fp_line (start 0 0) (end 1 0) (stroke (width 0.1) (type dash_dot)) (layer "F.SilkS"))"#,
+  );
+  assert_eq!(line.stroke.line_type, StrokeType::DashDot);
+
+  // The fallback is reported like any other skipped token
+  let board = r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (gr_line (start 0 0) (end 1 0) (stroke (width 0.1) (type wavy)) (layer "Edge.Cuts"))
+)"#;
+  let (pcb, warnings) = parse_pcb_file_with_warnings(board, ParseOptions::default()).unwrap();
+  assert_eq!(pcb.graphics.len(), 1);
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].found, "wavy");
+}
+
+#[test]
+pub fn locked_flag() {
+  let line: FootprintLine = parse(
+    r#"(fp_line (start 0 0) (end 1 1) locked (stroke (width 0.1) (type solid)) (layer "F.SilkS"))"#,
+  );
+  assert!(line.locked);
+
+  let line: FootprintLine =
+    parse(r#"(fp_line (start 0 0) (end 1 1) (stroke (width 0.1) (type solid)) (layer "F.SilkS"))"#);
+  assert!(!line.locked);
+
+  let arc: FootprintArc =
+    parse(r#"(fp_arc locked (start 0 0) (mid 1 1) (end 2 0) (layer "F.SilkS"))"#);
+  assert!(arc.locked);
+
+  let pad: Pad = parse(r#"(pad "1" smd rect locked (at 0 0) (size 1 1) (layers "F.Cu"))"#);
+  assert!(pad.locked);
+}
+
+#[test]
+pub fn text_unlocked_and_hide() {
+  let text: FootprintText =
+    parse(r#"(fp_text user "A" (at 0 0) unlocked (layer "F.SilkS") (effects (font (size 1 1))))"#);
+  assert!(text.unlocked);
+  assert!(!text.hide);
+
+  let text: FootprintText = parse(
+    r#"(fp_text user "A" (at 0 0) unlocked (layer "F.SilkS") hide (effects (font (size 1 1))))"#,
+  );
+  assert!(text.unlocked);
+  assert!(text.hide);
+
+  let text: FootprintText =
+    parse(r#"(fp_text user "A" (at 0 0) (layer "F.SilkS") hide (effects (font (size 1 1))))"#);
+  assert!(!text.unlocked);
+  assert!(text.hide);
+
+  let text: FootprintText =
+    parse(r#"(fp_text user "A" (at 0 0) (unlocked yes) (layer "F.SilkS") (hide no))"#);
+  assert!(text.unlocked);
+  assert!(!text.hide);
+}
+
+#[test]
+pub fn arc_bounding_box() {
+  let arc = |from: f64, to: f64| -> FootprintArc {
+    let point = |degrees: f64| {
+      let (sin, cos) = degrees.to_radians().sin_cos();
+      format!("{cos} {sin}")
+    };
+    parse(&format!(
+      r#"(fp_arc (start {}) (mid {}) (end {}) (layer "F.SilkS"))"#,
+      point(from),
+      point((from + to) / 2.),
+      point(to)
+    ))
+  };
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+  // A quarter circle reaching its top between the three points
+  let bbox = arc(30., 120.).bounding_box();
+  assert!(
+    close(bbox.width(), 30f64.to_radians().cos() + 0.5),
+    "{bbox:?}"
+  );
+  assert!(close(bbox.height(), 0.5), "{bbox:?}");
+  assert!(close(bbox.y() + bbox.height(), 1.0), "{bbox:?}");
+
+  // A half circle passing both the top and the left extremes
+  let bbox = arc(30., 210.).bounding_box();
+  assert!(close(bbox.x(), -1.0), "{bbox:?}");
+  assert!(
+    close(bbox.width(), 30f64.to_radians().cos() + 1.0),
+    "{bbox:?}"
+  );
+  assert!(close(bbox.height(), 1.5), "{bbox:?}");
+}
+
+#[test]
+pub fn polygon_and_curve_bounding_boxes() {
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+  // Rounded end bulging to x = 2 between the arc's start and end
+  let polygon: FootprintPolygon = parse(
+    r#"(fp_poly (pts (xy 0 -1) (xy 1 -1) (arc (start 1 -1) (mid 2 0) (end 1 1)) (xy 0 1))
+      (layer "F.Cu"))"#,
+  );
+  let bbox = polygon.bounding_box();
+  assert!(close(bbox.x(), 0.0) && close(bbox.width(), 2.0), "{bbox:?}");
+  assert!(
+    close(bbox.y(), -1.0) && close(bbox.height(), 2.0),
+    "{bbox:?}"
+  );
+
+  let curve: FootprintCurve =
+    parse(r#"(fp_curve (pts (xy 0 0) (xy 0 1) (xy 1 1) (xy 1 0)) (layer "F.SilkS"))"#);
+  let bbox = curve.bounding_box();
+  assert!(close(bbox.x(), 0.0) && close(bbox.width(), 1.0), "{bbox:?}");
+  assert!(
+    close(bbox.y(), 0.0) && close(bbox.height(), 0.75),
+    "{bbox:?}"
+  );
+}
+
+#[test]
+pub fn areas() {
+  let square: FootprintPolygon =
+    parse(r#"(fp_poly (pts (xy 0 0) (xy 1 0) (xy 1 1) (xy 0 1)) (layer "F.Cu"))"#);
+  assert!((square.area() - 1.0).abs() < 1e-12);
+
+  let rect: FootprintRectangle = parse(r#"(fp_rect (start 1 1) (end -1 0) (layer "F.Cu"))"#);
+  assert!((rect.area() - 2.0).abs() < 1e-12);
+
+  let circle: FootprintCircle = parse(r#"(fp_circle (center 5 5) (end 6 5) (layer "F.Cu"))"#);
+  assert!((circle.area() - std::f64::consts::PI).abs() < 1e-12);
+
+  // A unit disc drawn as two half circles
+  let disc: FootprintPolygon = parse(
+    r#"(fp_poly (pts (arc (start 1 0) (mid 0 1) (end -1 0)) (arc (start -1 0) (mid 0 -1) (end 1 0)))
+      (layer "F.Cu"))"#,
+  );
+  assert!(
+    (disc.area() - std::f64::consts::PI).abs() < 1e-2,
+    "{}",
+    disc.area()
+  );
+
+  let stadium: FootprintPolygon = parse(
+    r#"(fp_poly (pts (xy 0 -1) (xy 2 -1) (arc (start 2 -1) (mid 3 0) (end 2 1)) (xy 0 1))
+      (layer "F.Cu"))"#,
+  );
+  let expected = 4.0 + std::f64::consts::PI / 2.0;
+  assert!(
+    (stadium.area() - expected).abs() < 1e-2,
+    "{}",
+    stadium.area()
+  );
+}
+
+#[test]
+pub fn text_bounding_box() {
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+  // 5 characters at 1mm: 3mm wide, centered on the anchor
+  let text: FootprintText = parse(
+    r#"(fp_text user "HELLO" (at 10 20) (layer "F.SilkS") (effects (font (size 1 1) (thickness 0.15))))"#,
+  );
+  let bbox = text.bounding_box();
+  assert!(close(bbox.min_x, 8.5) && close(bbox.max_x, 11.5));
+  assert!(close(bbox.min_y, 19.5) && close(bbox.max_y, 20.5));
+
+  let text: FootprintText = parse(
+    r#"(fp_text user "HELLO" (at 10 20) (layer "F.SilkS")
+      (effects (font (size 1 1) (thickness 0.15)) (justify left bottom)))"#,
+  );
+  let bbox = text.bounding_box();
+  assert!(close(bbox.min_x, 10.0) && close(bbox.max_x, 13.0));
+  assert!(close(bbox.min_y, 19.0) && close(bbox.max_y, 20.0));
+
+  // Rotated by 90°, the box stands upright
+  let text: FootprintText = parse(
+    r#"(fp_text user "HELLO" (at 10 20 90) (layer "F.SilkS") (effects (font (size 1 1) (thickness 0.15))))"#,
+  );
+  let bbox = text.bounding_box();
+  assert!(close(bbox.width(), 1.0) && close(bbox.height(), 3.0));
+
+  // Font sizes are written height first, so wide glyphs only stretch the box sideways
+  let text: FootprintText = parse(
+    r#"(fp_text user "HELLO" (at 10 20) (layer "F.SilkS") (effects (font (size 1 2) (thickness 0.15))))"#,
+  );
+  let bbox = text.bounding_box();
+  assert!(
+    close(bbox.width(), 6.0) && close(bbox.height(), 1.0),
+    "{bbox:?}"
+  );
+}
+
+#[test]
+pub fn shape_fill() {
+  use kicad_parser::sexpr::ToSExpr;
+
+  let rect: FootprintRectangle = parse(
+    r#"(fp_rect (start 0 0) (end 1 1) (stroke (width 0.1) (type solid)) (fill solid) (layer "F.SilkS"))"#,
+  );
+  assert_eq!(rect.fill, FillType::Solid);
+  let circle: FootprintCircle = parse(
+    r#"(fp_circle (center 0 0) (end 1 0) (stroke (width 0.1) (type solid)) (fill no) (layer "F.SilkS"))"#,
+  );
+  assert_eq!(circle.fill, FillType::None);
+  let poly: FootprintPolygon = parse(
+    r#"(fp_poly (pts (xy 0 0) (xy 1 0) (xy 1 1)) (stroke (width 0.1) (type solid)) (fill hatch) (layer "F.SilkS"))"#,
+  );
+  assert!(poly.fill.is_filled());
+
+  let circle: FootprintCircle = parse(
+    r#"(fp_circle (center 0 0) (end 1 0) (stroke (width 0.1) (type solid)) (layer "F.SilkS"))"#,
+  );
+  assert_eq!(circle.fill, FillType::None);
+
+  for fill in [
+    FillType::None,
+    FillType::Solid,
+    FillType::Hatch {
+      spacing: Some(0.5),
+      thickness: None,
+    },
+  ] {
+    let mut out = String::new();
+    fill.to_sexpr().write(&mut out, 0);
+    assert_eq!(parse::<FillType>(&out), fill);
+  }
+}
+
+#[test]
+pub fn board_graphics() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (gr_arc (start 0 0) (mid 1 1) (end 2 0) (layer "Dwgs.User") (width 0.1))
+      (gr_circle (center 5 5) (end 6 5) (stroke (width 0.1) (type solid)) (fill none) (layer "F.SilkS"))
+      (gr_rect (start 0 0) (end 3 2) (stroke (width 0.1) (type solid)) (fill none) (layer "Edge.Cuts"))
+      (gr_line (start 0 0) (end 4 0) (stroke (width 0.1) (type solid)) (layer "Edge.Cuts"))
+    )"#,
+  )
+  .unwrap();
+
+  let [arc, circle, rect, line] = pcb.graphics.as_slice() else {
+    panic!("expected 4 graphics, got {:?}", pcb.graphics);
+  };
+  let Graphic::Arc(arc) = arc else {
+    panic!("expected an arc, got {arc:?}");
+  };
+  assert_eq!(
+    (arc.start, arc.mid, arc.end),
+    (Point::new(0., 0.), Point::new(1., 1.), Point::new(2., 0.))
+  );
+  assert_eq!(arc.width, 0.1);
+  assert_eq!(arc.layer, "Dwgs.User");
+  assert!(matches!(circle, Graphic::Circle(circle) if circle.center == Point::new(5., 5.)));
+  assert!(matches!(rect, Graphic::Rectangle(rect) if rect.end == Point::new(3., 2.)));
+  assert!(matches!(line, Graphic::Line(line) if line.end == Point::new(4., 0.)));
+
+  // The prefix must be one of `fp_` and `gr_`
+  let arc = parse_sexpr("(xx_arc (start 0 0) (mid 1 1) (end 2 0))").unwrap();
+  assert!(arc.as_sexpr_into::<FootprintArc>().is_err());
+}
+
+#[test]
+pub fn legacy_width() {
+  let line: FootprintLine =
+    parse(r#"(fp_line (start 0 0) (end 1 0) (layer "F.SilkS") (width 0.25))"#);
+  assert_eq!(line.effective_width(), 0.25);
+  let line: FootprintLine = parse(
+    r#"(fp_line (start 0 0) (end 1 0) (stroke (width 0.12) (type solid)) (layer "F.SilkS"))"#,
+  );
+  assert_eq!(line.effective_width(), 0.12);
+
+  let rect: FootprintRectangle =
+    parse(r#"(fp_rect (start 0 0) (end 1 1) (layer "F.SilkS") (width 0.5))"#);
+  assert_eq!(rect.effective_width(), 0.5);
+  let circle: FootprintCircle =
+    parse(r#"(fp_circle (center 0 0) (end 1 0) (layer "F.SilkS") (width 0.5))"#);
+  assert_eq!(circle.effective_width(), 0.5);
+  let arc: FootprintArc =
+    parse(r#"(fp_arc (start 0 0) (mid 1 1) (end 2 0) (layer "F.SilkS") (width 0.5))"#);
+  assert_eq!(arc.effective_width(), 0.5);
+  let poly: FootprintPolygon =
+    parse(r#"(fp_poly (pts (xy 0 0) (xy 1 0) (xy 1 1)) (layer "F.SilkS") (width 0.5))"#);
+  assert_eq!(poly.effective_width(), 0.5);
+  assert_eq!(Graphic::Polygon(poly).effective_width(), Some(0.5));
+}
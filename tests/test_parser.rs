@@ -0,0 +1,14 @@
+use kicad_parser::{parser::ParserErrorKind, read_utf8};
+
+#[test]
+pub fn utf8_bom_is_stripped() {
+  let content = read_utf8(b"\xEF\xBB\xBF(kicad_pcb)").unwrap();
+  assert_eq!(content, "(kicad_pcb)");
+}
+
+#[test]
+pub fn utf8_error_names_offset() {
+  let error = read_utf8(b"(kicad_pcb \xFF)").unwrap_err();
+  assert_eq!(error.kind, ParserErrorKind::InvalidEncoding);
+  assert!(error.found.contains("offset 11"), "{}", error.found);
+}
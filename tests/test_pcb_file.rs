@@ -10,7 +10,7 @@ pub fn sexpr() {
     "Failed to parse s-expression from board.kicad_pcb"
   );
 
-  fs::write("./out.txt", format!("{:?}", parse_sexpr(CONTENT).unwrap())).unwrap();
+  fs::write("./out.txt", parse_sexpr(CONTENT).unwrap().to_string()).unwrap();
 }
 
 #[test]
@@ -28,3 +28,1228 @@ pub fn pcb_file() {
   )
   .unwrap();
 }
+
+#[test]
+pub fn all_text() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let texts = pcb.all_text(false);
+  let (_, position, layer) = texts
+    .iter()
+    .find(|(text, ..)| text == "EXTERNAL POWER")
+    .expect("Silkscreen label should be found");
+  assert_eq!(layer, "F.SilkS");
+  assert_eq!((position.x, position.y), (78.5, 82.3575));
+
+  // Footprint text is reported in board coordinates
+  assert!(
+    texts
+      .iter()
+      .any(|(text, position, _)| text == "${REFERENCE}" && position.x == 123.0)
+  );
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (gr_text_box "shown" (start 0 0) (end 10 5) (layer "Cmts.User")
+        (effects (font (size 1 1))))
+      (gr_text_box "hidden" (start 0 10) (end 10 15) (layer "Cmts.User")
+        (effects (font (size 1 1)) (hide yes)))
+    )"#,
+  )
+  .unwrap();
+  let names = |include_hidden| {
+    pcb
+      .all_text(include_hidden)
+      .into_iter()
+      .map(|(text, ..)| text)
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(names(false), ["shown"]);
+  assert_eq!(names(true), ["shown", "hidden"]);
+}
+
+#[test]
+pub fn generator_version() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.generator_version.as_deref(), Some("9.0"));
+
+  let pcb = parse_pcb_file("(kicad_pcb (version 20211014) (generator pcbnew))").unwrap();
+  assert_eq!(pcb.version, "20211014");
+  assert_eq!(pcb.generator, "pcbnew");
+  assert_eq!(pcb.generator_version, None);
+}
+
+#[test]
+pub fn net_display_name() {
+  use kicad_parser::pcb_file::{parse_pcb_file, unescape_net_name};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let net = pcb.nets.iter().find(|net| net.ordinal == 10).unwrap();
+  assert_eq!(net.name, "unconnected-(U3-MTDO{slash}IO39-Pad32)");
+  assert_eq!(net.display_name(), "unconnected-(U3-MTDO/IO39-Pad32)");
+
+  assert_eq!(
+    unescape_net_name("A{dblquote}B{unknown}{"),
+    "A\"B{unknown}{"
+  );
+}
+
+#[test]
+pub fn copper_layers() {
+  use kicad_parser::pcb_file::{PcbLayerType, parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let names: Vec<&str> = pcb
+    .copper_layers()
+    .iter()
+    .map(|layer| layer.name.as_str())
+    .collect();
+  assert_eq!(names, vec!["F.Cu", "B.Cu"]);
+  assert_eq!(pcb.layers_of_type(PcbLayerType::Signal).len(), 2);
+  assert_eq!(pcb.layers_of_type(PcbLayerType::Power).len(), 0);
+}
+
+#[test]
+pub fn plot_params() {
+  use kicad_parser::{pcb_file::PcbPlotParams, sexpr::parse_sexpr};
+
+  let start = CONTENT.find("(pcbplotparams").unwrap();
+  let end = start + CONTENT[start..].find("\n\t\t)").unwrap() + 4;
+  let params: PcbPlotParams = parse_sexpr(&CONTENT[start..end])
+    .unwrap()
+    .as_sexpr_into()
+    .unwrap();
+
+  assert_eq!(params.layer_selection, Some(0x55555555_5755f5ff));
+  assert_eq!(params.mode, Some(1));
+  assert!(!params.use_aux_origin);
+  assert_eq!(params.hpgl_pen_number, Some(1));
+  assert!(params.dxf_polygon_mode);
+  assert!(!params.sketch_pads_on_fab);
+  assert!(!params.subtract_mask_from_silk);
+  assert_eq!(params.drill_shape, Some(0));
+  assert_eq!(params.scale_selection, Some(1));
+  assert_eq!(
+    params.output_directory.as_deref(),
+    Some("D:/QualityProducts/Inventarisatiesysteem/Kicad/Main Board/PCB/")
+  );
+  assert!(params.other.contains_key("pdf_metadata"));
+}
+
+#[test]
+pub fn segments() {
+  use kicad_parser::{common::GetBoundingBox, pcb_file::parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.segments.len(), 116);
+
+  let segment = &pcb.segments[0];
+  assert_eq!((segment.start.x, segment.start.y), (86.5085, 104.101));
+  assert_eq!((segment.end.x, segment.end.y), (90.999, 104.101));
+  assert_eq!(segment.width, 0.2);
+  assert_eq!(segment.layer, *"F.Cu");
+  assert_eq!(segment.net, 1);
+  assert_eq!(segment.uuid.0, "0452857c-5435-4ecf-804b-fbcc21175160");
+
+  let bbox = segment.bounding_box();
+  assert!((bbox.min_y - 104.001).abs() < 1e-9);
+  assert!((bbox.max_x - 91.099).abs() < 1e-9);
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (gr_rect (start 0 0) (end 10 10) (stroke (width 0) (type default)) (layer "Edge.Cuts"))
+      (segment (start 5 5) (end 15 5) (width 0.5) (layer "F.Cu") (net 1) (uuid "s1"))
+      (via (at 5 -4) (size 1) (drill 0.4) (layers "F.Cu" "B.Cu") (net 1) (uuid "v1"))
+    )"#,
+  )
+  .unwrap();
+  let bbox = pcb.bounding_box();
+  assert!((bbox.max_x - 15.25).abs() < 1e-9);
+  assert!((bbox.min_y + 4.5).abs() < 1e-9);
+  assert_eq!((bbox.min_x, bbox.max_y), (0., 10.));
+}
+
+#[test]
+pub fn net_class_for() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20171130) (generator pcbnew)
+      (net 0 "")
+      (net 1 "GND")
+      (net 2 "VBUS")
+      (net 3 "SDA" (net_class "Signal"))
+      (net_class Default "This is the default net class."
+        (clearance 0.2)
+        (trace_width 0.25)
+        (add_net "SDA")
+      )
+      (net_class Power ""
+        (clearance 0.3)
+        (trace_width 0.5)
+        (add_net "VBUS")
+      )
+      (net_class Signal "" (clearance 0.15))
+    )"#,
+  )
+  .unwrap();
+
+  assert_eq!(pcb.net_classes.len(), 3);
+  let power = pcb.net_class_for("VBUS").unwrap();
+  assert_eq!(power.name, "Power");
+  assert_eq!(power.trace_width, Some(0.5));
+  assert_eq!(pcb.net_class_for("GND").unwrap().name, "Default");
+  assert_eq!(pcb.net_class_for("SDA").unwrap().name, "Signal");
+}
+
+#[test]
+pub fn vias() {
+  use kicad_parser::pcb_file::{ViaType, parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.vias.len(), 16);
+
+  let via = &pcb.vias[0];
+  assert_eq!(via.via_type, ViaType::Through);
+  assert_eq!((via.position.x, via.position.y), (80.55, 95.85));
+  assert_eq!((via.size, via.drill), (0.6, 0.3));
+  assert_eq!(via.net, 2);
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (via blind (at 1 2) (size 0.45) (drill 0.2) (layers "In2.Cu" "F.Cu") (net 3) (uuid "a"))
+      (via micro (at 3 4) (size 0.3) (drill 0.1) (layers "F.Cu" "In1.Cu") (net 0) (uuid "b"))
+    )"#,
+  )
+  .unwrap();
+  assert_eq!(pcb.vias[0].via_type, ViaType::BlindBuried);
+  assert_eq!(pcb.vias[0].layers[0], *"In2.Cu");
+  assert_eq!(pcb.vias[0].layers[1], *"F.Cu");
+  assert_eq!(pcb.vias[1].via_type, ViaType::Micro);
+}
+
+#[test]
+pub fn zero_width_graphics() {
+  use kicad_parser::{common::Graphic, pcb_file::parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert!(pcb.zero_width_graphics().is_empty());
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (gr_line (start 0 0) (end 1 0) (stroke (width 0) (type solid)) (layer "F.SilkS"))
+      (gr_line (start 0 1) (end 1 1) (stroke (width 0.1) (type solid)) (layer "F.SilkS"))
+      (gr_line (start 0 2) (end 1 2) (width 0.15) (layer "F.SilkS"))
+      (gr_rect (start 0 0) (end 1 1) (stroke (width 0) (type solid)) (fill yes) (layer "F.Cu"))
+      (gr_text "label" (at 0 0) (layer "F.SilkS"))
+    )"#,
+  )
+  .unwrap();
+
+  let flagged = pcb.zero_width_graphics();
+  assert_eq!(flagged.len(), 1);
+  assert!(matches!(flagged[0], Graphic::Line(line) if line.start.y == 0.0));
+}
+
+#[test]
+pub fn round_trip() {
+  use kicad_parser::{
+    common::{CustomPadClearance, PadProperty, PadShape},
+    parser::ParseOptions,
+    pcb_file::{parse_pcb_file, parse_pcb_file_with, write_pcb_file},
+  };
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let written = write_pcb_file(&pcb);
+  assert!(written.starts_with("(kicad_pcb\n\t(version 20241229)\n\t(generator \"pcbnew\")\n"));
+
+  let reparsed = parse_pcb_file(&written).unwrap();
+  assert_eq!(reparsed, pcb);
+  assert_eq!(write_pcb_file(&reparsed), written);
+
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  let pcb = parse_pcb_file_with(
+    r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (footprint "R" (layer "F.Cu") (autoplace_cost90 3) (autoplace_cost180 5)
+    (pad "1" thru_hole custom (at 0 0) (size 1 1) (drill 0.5) (property pad_prop_castellated)
+      (layers "*.Cu") (remove_unused_layers yes) (keep_end_layers yes)
+      (options (clearance outline) (anchor circle)))
+    (pad "2" thru_hole circle (at 2 0) (size 1 1) (drill 0.5) (layers "*.Cu")
+      (remove_unused_layers) (keep_end_layers)))
+)"#,
+    strict,
+  )
+  .unwrap();
+
+  let footprint = &pcb.footprints[0];
+  assert_eq!(footprint.autoplace_cost90, Some(3));
+  assert_eq!(footprint.autoplace_cost180, Some(5));
+  let pad = &footprint.pads[0];
+  assert!(pad.remove_unused_layers && pad.keep_end_layers);
+  assert_eq!(pad.properties, vec![PadProperty::Castellated]);
+  let options = pad.custom_options.as_ref().unwrap();
+  assert_eq!(options.clearance, CustomPadClearance::Outline);
+  assert_eq!(options.anchor, PadShape::Circle);
+  assert!(footprint.pads[1].remove_unused_layers && footprint.pads[1].keep_end_layers);
+
+  assert_eq!(
+    parse_pcb_file_with(&write_pcb_file(&pcb), strict).unwrap(),
+    pcb
+  );
+}
+
+#[test]
+pub fn copper_layer_count() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.copper_layer_count(), 2);
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (layers (0 "F.Cu" signal) (1 "In1.Cu" power) (2 "In2.Cu" mixed) (31 "B.Cu" signal) (44 "Edge.Cuts" user))
+    )"#,
+  )
+  .unwrap();
+  assert_eq!(pcb.copper_layer_count(), 4);
+}
+
+#[test]
+pub fn parse_directory() {
+  let dir = std::env::temp_dir().join(format!("kicad-parser-dir-{}", std::process::id()));
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(dir.join("good.kicad_pcb"), CONTENT).unwrap();
+  fs::write(dir.join("broken.kicad_pcb"), "(kicad_pcb (version").unwrap();
+  fs::write(dir.join("notes.txt"), "not a board").unwrap();
+
+  let results = kicad_parser::parse_directory(&dir, "kicad_pcb");
+  fs::remove_dir_all(&dir).unwrap();
+
+  assert_eq!(results.len(), 2);
+  assert!(results[0].0.ends_with("broken.kicad_pcb"));
+  assert!(results[0].1.is_err());
+  assert!(results[1].0.ends_with("good.kicad_pcb"));
+  assert!(results[1].1.is_ok());
+}
+
+#[test]
+pub fn iterate_footprints() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let mut count = 0;
+  for footprint in &pcb {
+    assert!(footprint.library_link.is_some());
+    count += 1;
+  }
+  assert!(count > 0);
+  assert_eq!(count, pcb.footprints.len());
+}
+
+#[test]
+pub fn setup() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.setup.pad_to_mask_clearance, 0.0);
+  assert_eq!(pcb.setup.solder_mask_min_width, None);
+  let params = pcb.setup.plot_params.as_ref().unwrap();
+  assert_eq!(params.layer_selection, Some(0x55555555_5755f5ff));
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (setup
+        (pad_to_mask_clearance 0.05)
+        (solder_mask_min_width 0.1)
+        (pad_to_paste_clearance -0.03)
+        (pad_to_paste_clearance_ratio -0.1)
+        (aux_axis_origin 100 50.5)
+        (grid_origin 10 20)
+      )
+    )"#,
+  )
+  .unwrap();
+  assert_eq!(pcb.setup.pad_to_mask_clearance, 0.05);
+  assert_eq!(pcb.setup.solder_mask_min_width, Some(0.1));
+  assert_eq!(pcb.setup.pad_to_paste_clearance, Some(-0.03));
+  assert_eq!(pcb.setup.pad_to_paste_clearance_ratio, Some(-0.1));
+  assert_eq!(pcb.setup.aux_axis_origin, Some((100.0, 50.5)));
+  assert_eq!(pcb.setup.grid_origin, Some((10.0, 20.0)));
+  assert!(pcb.setup.plot_params.is_none());
+}
+
+#[test]
+pub fn stackup() {
+  use kicad_parser::{
+    parser::ParseOptions,
+    pcb_file::{
+      EdgeConnectorSetting, parse_pcb_file, parse_pcb_file_with, parse_pcb_file_with_warnings,
+      write_pcb_file,
+    },
+  };
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (setup
+        (stackup
+          (layer "F.SilkS" (type "Top Silk Screen"))
+          (layer "F.Cu" (type "copper") (thickness 0.035))
+          (layer "dielectric 1" (type "core") (thickness 0.1) (material "FR4") (epsilon_r 4.5) (loss_tangent 0.02))
+          (layer "B.Cu" (type "copper") (thickness 0.035))
+          (copper_finish "ENIG")
+          (dielectric_constraints no)
+          (edge_connector bevelled)
+          (castellated_pads yes)
+        )
+        (pad_to_mask_clearance 0)
+      )
+    )"#,
+  )
+  .unwrap();
+
+  let stackup = pcb.setup.stack_up_settings.as_ref().unwrap();
+  assert_eq!(stackup.layers.len(), 4);
+  let dielectric = &stackup.layers[2];
+  assert_eq!(dielectric.name, "dielectric 1");
+  assert_eq!(dielectric.layer_type, "core");
+  assert_eq!(dielectric.thickness, Some(0.1));
+  assert_eq!(dielectric.material.as_deref(), Some("FR4"));
+  assert_eq!(dielectric.epsilon_r, Some(4.5));
+  assert_eq!(dielectric.loss_tangent, Some(0.02));
+  assert_eq!(stackup.layers[0].thickness, None);
+  assert_eq!(stackup.copper_finish.as_deref(), Some("ENIG"));
+  assert_eq!(stackup.dielectric_constraints, Some(false));
+  assert!(matches!(
+    stackup.edge_connector,
+    Some(EdgeConnectorSetting::Bevelled)
+  ));
+  assert_eq!(stackup.castellated_pads, Some(true));
+  assert_eq!(stackup.edge_plating, None);
+
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+
+  let unknown = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (setup (stackup (edge_connector sideways)))
+    )"#;
+  let (pcb, warnings) = parse_pcb_file_with_warnings(unknown, ParseOptions::default()).unwrap();
+  let stackup = pcb.setup.stack_up_settings.as_ref().unwrap();
+  assert!(stackup.edge_connector.is_none());
+  assert_eq!(warnings.len(), 1);
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  assert!(parse_pcb_file_with(unknown, strict).is_err());
+}
+
+#[test]
+pub fn duplicate_net_ordinals() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert!(pcb.duplicate_net_ordinals().is_empty());
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (net 0 "")
+      (net 3 "GND")
+      (net 1 "VCC")
+      (net 3 "SDA")
+      (net 1 "SCL")
+      (net 3 "SDA")
+    )"#,
+  )
+  .unwrap();
+  assert_eq!(pcb.duplicate_net_ordinals(), vec![1, 3]);
+}
+
+#[test]
+pub fn reordered_layers() {
+  use kicad_parser::pcb_file::{PcbLayerType, parse_pcb_file};
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator freerouting)
+      (layers
+        (0 signal "F.Cu")
+        (31 B.Cu signal)
+        (44 "Edge.Cuts")
+        (45 user "User.1" "Mechanical")
+      )
+    )"#,
+  )
+  .unwrap();
+
+  let layers: Vec<_> = pcb
+    .layers
+    .iter()
+    .map(|layer| (layer.ordinal, layer.name.as_str(), layer.layer_type.clone()))
+    .collect();
+  assert_eq!(
+    layers,
+    vec![
+      (0, "F.Cu", PcbLayerType::Signal),
+      (31, "B.Cu", PcbLayerType::Signal),
+      (44, "Edge.Cuts", PcbLayerType::User),
+      (45, "User.1", PcbLayerType::User),
+    ]
+  );
+  assert_eq!(pcb.layers[3].user_name.as_deref(), Some("Mechanical"));
+
+  let error =
+    parse_pcb_file(r#"(kicad_pcb (version 20240108) (generator freerouting) (layers (0 signal)))"#)
+      .unwrap_err();
+  assert!(error.found.contains("without a name"), "{error:?}");
+}
+
+#[test]
+pub fn zones() {
+  use kicad_parser::{
+    common::{PointItem, ZoneHatchStyle},
+    pcb_file::parse_pcb_file,
+  };
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.zones.len(), 1);
+
+  let zone = &pcb.zones[0];
+  assert_eq!(zone.net, 2);
+  assert_eq!(zone.net_name, "GND");
+  let layers: Vec<_> = zone.layers.iter().map(|layer| layer.as_str()).collect();
+  assert_eq!(layers, vec!["F.Cu", "B.Cu"]);
+  let hatch = zone.hatch.as_ref().unwrap();
+  assert_eq!((&hatch.style, hatch.pitch), (&ZoneHatchStyle::Edge, 0.5));
+  assert_eq!(zone.connect_pads.as_ref().unwrap().clearance, 0.5);
+  assert_eq!(zone.connect_pads.as_ref().unwrap().connection, None);
+  assert_eq!(zone.min_thickness, Some(0.25));
+
+  assert_eq!(zone.polygon.0.len(), 4);
+  assert_eq!(
+    zone.polygon.0[1],
+    PointItem::Point(kicad_parser::common::Point::new(125.5, 70.))
+  );
+  assert!(!zone.filled_polygons.is_empty());
+  assert_eq!(zone.filled_polygons[0].layer, "F.Cu");
+  assert!(zone.filled_polygons[0].points.0.len() > 100);
+}
+
+#[test]
+pub fn canonicalize() {
+  use kicad_parser::pcb_file::{parse_pcb_file, write_pcb_file};
+
+  let first = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+    (layers (0 "F.Cu" signal) (31 "B.Cu" signal) (44 "Edge.Cuts" user))
+    (net 0 "") (net 1 "GND") (net 2 "VCC")
+    (footprint "R" (layer "F.Cu") (uuid "b") (at 10 20)
+      (property "Reference" "R2")
+      (pad "1" smd rect (at -1 0) (size 1 1) (layers "F.Cu") (uuid "p1"))
+      (pad "2" smd rect (at 1 0) (size 1 1) (layers "F.Cu") (uuid "p2")))
+    (footprint "R" (layer "F.Cu") (uuid "a") (at 30 20)
+      (property "Reference" "R1")
+      (zone (net 0) (net_name "") (layer "F.Cu") (uuid "z1")
+        (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1))))
+      (zone (net 0) (net_name "") (layer "B.Cu") (uuid "z2")
+        (polygon (pts (xy 0 0) (xy 2 0) (xy 2 2)))))
+    (gr_line (start 0 0) (end 10 0) (layer "Edge.Cuts") (uuid "g1"))
+    (gr_line (start 10 0) (end 10 10) (layer "Edge.Cuts") (uuid "g2"))
+    (segment (start 0 0) (end 1 1) (width 0.25) (layer "F.Cu") (net 1) (uuid "s1"))
+    (segment (start 1 1) (end 2 1) (width 0.25) (layer "F.Cu") (net 1) (uuid "s2"))
+  )"#;
+  let second = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+    (layers (44 "Edge.Cuts" user) (0 "F.Cu" signal) (31 "B.Cu" signal))
+    (net 2 "VCC") (net 0 "") (net 1 "GND")
+    (footprint "R" (layer "F.Cu") (uuid "a") (at 30.0000000001 20)
+      (property "Reference" "R1")
+      (zone (net 0) (net_name "") (layer "B.Cu") (uuid "z2")
+        (polygon (pts (xy 0 0) (xy 2.0000000001 0) (xy 2 2))))
+      (zone (net 0) (net_name "") (layer "F.Cu") (uuid "z1")
+        (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1)))))
+    (footprint "R" (layer "F.Cu") (uuid "b") (at 10 19.9999999999)
+      (property "Reference" "R2")
+      (pad "2" smd rect (at 1 0) (size 1 1) (layers "F.Cu") (uuid "p2"))
+      (pad "1" smd rect (at -1 0) (size 1 1) (layers "F.Cu") (uuid "p1")))
+    (gr_line (start 10 0) (end 10 10) (layer "Edge.Cuts") (uuid "g2"))
+    (gr_line (start 0 0) (end 10.00000000002 0) (layer "Edge.Cuts") (uuid "g1"))
+    (segment (start 1 1) (end 2 1) (width 0.25) (layer "F.Cu") (net 1) (uuid "s2"))
+    (segment (start 0 0) (end 1 1) (width 0.25) (layer "F.Cu") (net 1) (uuid "s1"))
+  )"#;
+
+  let mut first = parse_pcb_file(first).unwrap();
+  let mut second = parse_pcb_file(second).unwrap();
+  assert_ne!(write_pcb_file(&first), write_pcb_file(&second));
+
+  first.canonicalize();
+  second.canonicalize();
+  assert_eq!(write_pcb_file(&first), write_pcb_file(&second));
+  assert_eq!(first.footprints[0].reference(), Some("R1"));
+  assert_eq!(first.footprints[1].pads[0].number, "1");
+  assert_eq!(first.footprints[0].zones[0].uuid.0, "z1");
+}
+
+#[test]
+pub fn groups() {
+  use kicad_parser::{common::Uuid, pcb_file::parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  assert_eq!(pcb.groups.len(), 1);
+  let group = &pcb.groups[0];
+  assert_eq!(group.name, "");
+  assert_eq!(
+    group.uuid,
+    Uuid("d53bb647-f045-4a3d-b434-5baca39a2643".to_string())
+  );
+  assert!(group.members.len() > 30);
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (footprint "R" (layer "F.Cu") (uuid "fp-1") (property "Reference" "R1"))
+      (footprint "R" (layer "F.Cu") (uuid "fp-2") (property "Reference" "R2"))
+      (footprint "R" (layer "F.Cu") (uuid "fp-3") (property "Reference" "R3"))
+      (gr_line (start 0 0) (end 1 0) (layer "F.SilkS") (uuid "line-1"))
+      (group "Power" (id "group-1") (members "fp-3" "line-1" "fp-1"))
+    )"#,
+  )
+  .unwrap();
+
+  let group = &pcb.groups[0];
+  assert_eq!(group.name, "Power");
+  assert_eq!(group.uuid, Uuid("group-1".to_string()));
+  let references: Vec<_> = pcb
+    .group_members(group)
+    .into_iter()
+    .map(|footprint| footprint.reference().unwrap())
+    .collect();
+  assert_eq!(references, vec!["R1", "R3"]);
+}
+
+#[test]
+pub fn parse_from_path_and_reader() {
+  use kicad_parser::{
+    parser::ParserErrorKind,
+    pcb_file::{parse_pcb_file, parse_pcb_file_from_path, parse_pcb_file_from_reader},
+  };
+  let expected = parse_pcb_file(CONTENT).unwrap();
+
+  let with_bom = [b"\xEF\xBB\xBF".as_slice(), CONTENT.as_bytes()].concat();
+  let pcb = parse_pcb_file_from_reader(with_bom.as_slice()).unwrap();
+  assert_eq!(pcb, expected);
+
+  let path = std::env::temp_dir().join(format!(
+    "kicad-parser-path-{}.kicad_pcb",
+    std::process::id()
+  ));
+  fs::write(&path, &with_bom).unwrap();
+  let pcb = parse_pcb_file_from_path(&path);
+  fs::remove_file(&path).unwrap();
+  assert_eq!(pcb.unwrap(), expected);
+
+  let error = parse_pcb_file_from_path(&path).unwrap_err();
+  assert_eq!(error.kind, ParserErrorKind::Io);
+  assert!(
+    error
+      .in_context
+      .iter()
+      .any(|context| context.contains("kicad-parser-path"))
+  );
+}
+
+#[test]
+pub fn parse_from_bytes() {
+  use kicad_parser::{
+    parser::ParserErrorKind,
+    pcb_file::{parse_pcb_file, parse_pcb_file_bytes},
+  };
+  let with_bom = [b"\xEF\xBB\xBF".as_slice(), CONTENT.as_bytes()].concat();
+  assert_eq!(
+    parse_pcb_file_bytes(&with_bom).unwrap(),
+    parse_pcb_file(CONTENT).unwrap()
+  );
+
+  let error =
+    parse_pcb_file_bytes(b"(kicad_pcb (version 20240108) (generator \"pcb\xC3\x28new\"))")
+      .unwrap_err();
+  assert_eq!(error.kind, ParserErrorKind::InvalidEncoding);
+  assert!(error.found.contains("offset 45"), "{error:?}");
+}
+
+#[test]
+pub fn version_round_trips_exactly() {
+  use kicad_parser::pcb_file::{parse_pcb_file, write_pcb_file};
+  let pcb = parse_pcb_file("(kicad_pcb (version 20240101) (generator pcbnew))").unwrap();
+  assert_eq!(pcb.version, "20240101");
+  assert!(write_pcb_file(&pcb).starts_with("(kicad_pcb\n\t(version 20240101)\n"));
+}
+
+#[test]
+pub fn title_block() {
+  use kicad_parser::pcb_file::{parse_pcb_file, write_pcb_file};
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew) (paper "A4")
+      (title_block (title "Main board") (date "2024-05-01") (rev "A") (company "ACME")
+        (comment 1 "First") (comment 4 "Fourth"))
+    )"#,
+  )
+  .unwrap();
+
+  let title_block = pcb.title_block.as_ref().unwrap();
+  assert_eq!(title_block.title.as_deref(), Some("Main board"));
+  assert_eq!(title_block.date.as_deref(), Some("2024-05-01"));
+  assert_eq!(title_block.rev.as_deref(), Some("A"));
+  assert_eq!(title_block.company.as_deref(), Some("ACME"));
+  assert_eq!(
+    title_block.comments,
+    vec![(1, "First".to_string()), (4, "Fourth".to_string())]
+  );
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+
+  // KiCad 5 leaves single words unquoted
+  let pcb = parse_pcb_file("(kicad_pcb (version 20171130) (title_block (rev B)))").unwrap();
+  assert_eq!(pcb.title_block.unwrap().rev.as_deref(), Some("B"));
+  assert!(parse_pcb_file(CONTENT).unwrap().title_block.is_none());
+}
+
+#[test]
+pub fn dimensions() {
+  use kicad_parser::{
+    common::{DimensionType, PointItem},
+    pcb_file::{parse_pcb_file, write_pcb_file},
+  };
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (dimension (type aligned) (layer "Dwgs.User") (uuid "dim-1")
+        (pts (xy 100 100) (xy 150 100))
+        (height -5)
+        (gr_text "50.0000 mm" (at 125 93.85 0) (layer "Dwgs.User") (uuid "dim-text")
+          (effects (font (size 1 1) (thickness 0.15))))
+        (format (prefix "") (suffix "") (units 3) (units_format 1) (precision 4))
+        (style (thickness 0.15) (arrow_length 1.27) (text_position_mode 0)
+          (extension_height 0.58642) (extension_offset 0.5) keep_text_aligned))
+      (dimension (type orthogonal) (locked yes) (layer "Cmts.User")
+        (pts (xy 0 0) (xy 10 20)) (height 2) (orientation 1)
+        (style (thickness 0.1) (arrow_length 1) (text_position_mode 2)))
+    )"#,
+  )
+  .unwrap();
+
+  assert_eq!(pcb.dimensions.len(), 2);
+  let aligned = &pcb.dimensions[0];
+  assert_eq!(aligned.dimension_type, DimensionType::Aligned);
+  assert_eq!(aligned.layer, "Dwgs.User");
+  assert_eq!(aligned.height, Some(-5.0));
+  assert!(matches!(aligned.points.0[1], PointItem::Point(point) if point.x == 150.0));
+  assert_eq!(aligned.text.as_ref().unwrap().text, "50.0000 mm");
+  assert_eq!(aligned.format.as_ref().unwrap().precision, 4);
+  assert!(aligned.style.keep_text_aligned);
+
+  let orthogonal = &pcb.dimensions[1];
+  assert_eq!(orthogonal.dimension_type, DimensionType::Orthogonal);
+  assert!(orthogonal.locked);
+  assert_eq!(orthogonal.orientation, Some(1));
+  assert!(orthogonal.text.is_none());
+
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+}
+
+#[test]
+pub fn pads_on_net() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+  let ground = pcb.pads_on_net(2);
+  assert!(!ground.is_empty());
+  assert!(
+    ground
+      .iter()
+      .all(|(_, pad)| pad.net_display_name().as_deref() == Some("GND"))
+  );
+  assert!(pcb.pad_net_mismatches().is_empty());
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (net 0 "") (net 1 "GND")
+      (footprint "R" (layer "F.Cu") (uuid "fp-1")
+        (pad "1" smd rect (at 0 0) (size 1 1) (net 1 "GND"))
+        (pad "2" smd rect (at 1 0) (size 1 1) (net 1 "VCC"))
+        (pad "3" smd rect (at 2 0) (size 1 1) (net 7 "Other"))
+        (pad "4" smd rect (at 3 0) (size 1 1)))
+    )"#,
+  )
+  .unwrap();
+
+  let numbers = |pads: Vec<(_, &kicad_parser::common::Pad)>| -> Vec<String> {
+    pads
+      .into_iter()
+      .map(|(_, pad)| pad.number.clone())
+      .collect()
+  };
+  assert_eq!(numbers(pcb.pads_on_net(1)), vec!["1", "2"]);
+  assert_eq!(numbers(pcb.pad_net_mismatches()), vec!["2", "3"]);
+  assert_eq!(pcb.footprints[0].pads[3].net_ordinal(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn board_to_json() {
+  use kicad_parser::pcb_file::{PcbFile, parse_pcb_file};
+  let pcb = parse_pcb_file(CONTENT).unwrap();
+
+  let json = serde_json::to_string(&pcb).unwrap();
+  assert!(json.contains(r#""generator":"pcbnew""#));
+
+  let back: PcbFile = serde_json::from_str(&json).unwrap();
+  assert_eq!(back.footprints.len(), pcb.footprints.len());
+  assert_eq!(back.nets, pcb.nets);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn json_round_trip() {
+  use kicad_parser::pcb_file::{PcbFile, parse_pcb_file};
+  let mut pcb = parse_pcb_file(CONTENT).unwrap();
+  // Raw s-expressions are not part of the dump
+  pcb.setup.plot_params.as_mut().unwrap().other.clear();
+  for zone in &mut pcb.zones {
+    zone.other.clear();
+  }
+
+  let json = pcb.to_json().unwrap();
+  assert!(json.contains(r#""layer":"F.Cu""#));
+  assert_eq!(PcbFile::from_json(&json).unwrap(), pcb);
+  assert!(PcbFile::from_json("{}").is_err());
+
+  let knockout = kicad_parser::common::Layer {
+    layer_name: "F.SilkS".to_string(),
+    knockout: true,
+  };
+  let json = serde_json::to_string(&knockout).unwrap();
+  assert_eq!(
+    serde_json::from_str::<kicad_parser::common::Layer>(&json).unwrap(),
+    knockout
+  );
+}
+
+#[test]
+pub fn board_text_box() {
+  use kicad_parser::{
+    common::Graphic,
+    pcb_file::{parse_pcb_file, write_pcb_file},
+  };
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (gr_text "label" (at 0 0) (layer "F.SilkS"))
+      (gr_text_box "Assembly notes" (start 10 10) (end 40 20) (layer "Cmts.User") (uuid "box")
+        (effects (font (size 1 1) (thickness 0.15)) (justify left top))
+        (border yes) (stroke (width 0.1) (type solid)))
+    )"#,
+  )
+  .unwrap();
+
+  assert!(matches!(&pcb.graphics[0], Graphic::Text(text) if text.text == "label"));
+  let Graphic::TextBox(text_box) = &pcb.graphics[1] else {
+    panic!("expected a text box, got {:?}", pcb.graphics[1]);
+  };
+  assert_eq!(text_box.text, "Assembly notes");
+  assert_eq!(text_box.border, Some(true));
+  assert_eq!(text_box.stroke.as_ref().unwrap().width, 0.1);
+
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+}
+
+#[test]
+pub fn error_location() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let input = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+  (net 0 "")
+  (footprint "R" (layer "F.Cu")
+    (pad "1" smd rect (at 0 0) (size 1 1))
+    (pad "2" smd rect (at 1 0) (size wide 1))
+  )
+)"#;
+
+  let error = parse_pcb_file(input).unwrap_err();
+  let span = error.span.unwrap();
+  assert_eq!(&input[span.start..span.end], "(size wide 1)");
+  assert_eq!(error.line_column(input), Some((5, 32)));
+  assert!(
+    error
+      .in_context
+      .iter()
+      .any(|context| context == "input line 5, column 32"),
+    "{error:?}"
+  );
+}
+
+#[test]
+pub fn parse_options() {
+  use kicad_parser::{
+    parser::ParseOptions,
+    pcb_file::{parse_pcb_file, parse_pcb_file_with, write_pcb_file},
+    sexpr::{SExpr, parse_sexpr},
+  };
+  let input = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+  (footprint "R" (layer "F.Cu")
+    (sheetname "Root")
+  )
+  (target plus (at 10 10) (size 5) (width 0.1) (layer "Edge.Cuts"))
+)"#;
+
+  let lenient = parse_pcb_file(input).unwrap();
+  assert!(lenient.unknown.is_empty());
+  assert!(lenient.footprints[0].unknown.is_empty());
+
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  assert!(parse_pcb_file_with(input, strict).is_err());
+
+  let collect = ParseOptions {
+    collect_unknown: true,
+    ..Default::default()
+  };
+  let pcb_file = parse_pcb_file_with(input, collect).unwrap();
+  assert_eq!(
+    pcb_file.unknown,
+    vec![SExpr::List(
+      parse_sexpr(r#"(target plus (at 10 10) (size 5) (width 0.1) (layer "Edge.Cuts"))"#).unwrap()
+    )]
+  );
+  assert_eq!(
+    pcb_file.footprints[0].unknown,
+    vec![SExpr::List(parse_sexpr(r#"(sheetname "Root")"#).unwrap())]
+  );
+  assert!(
+    parse_pcb_file_with(
+      input,
+      ParseOptions {
+        strict: true,
+        ..collect
+      }
+    )
+    .is_ok()
+  );
+
+  let written = write_pcb_file(&pcb_file);
+  assert!(written.contains("(target plus"), "{written}");
+  assert!(written.contains(r#"(sheetname "Root")"#), "{written}");
+
+  // The options only apply to the call they were passed to
+  assert!(parse_pcb_file(input).unwrap().unknown.is_empty());
+}
+
+#[test]
+pub fn fixed_point_parse() {
+  use kicad_parser::{
+    common::{Graphic, PointFixed},
+    parser::ParseOptions,
+    pcb_file::{parse_pcb_file, parse_pcb_file_with},
+  };
+  let board = |x: &str, y: &str| {
+    format!(
+      r#"(kicad_pcb (version 20240108) (generator pcbnew)
+  (gr_line (start {x} {y}) (end 10 {y}) (stroke (width 0.1) (type solid)) (layer "Edge.Cuts"))
+  (footprint "R" (layer "F.Cu") (at {x} {y})
+    (pad "1" smd rect (at {y} {x}) (size 0.5 0.5) (layers "F.Cu")))
+)"#
+    )
+  };
+  let plain = board("0.3", "12.7");
+  let noisy = board("0.30000000000000004", "12.700000001e0");
+  assert_ne!(
+    parse_pcb_file(&plain).unwrap(),
+    parse_pcb_file(&noisy).unwrap()
+  );
+
+  let fixed = ParseOptions {
+    fixed_point: true,
+    ..Default::default()
+  };
+  let pcb = parse_pcb_file_with(&noisy, fixed).unwrap();
+  assert_eq!(pcb, parse_pcb_file_with(&plain, fixed).unwrap());
+  assert_eq!(
+    pcb.footprints[0].pads[0].position.to_fixed(),
+    PointFixed {
+      x: 12_700_000,
+      y: 300_000
+    }
+  );
+  let Graphic::Line(line) = &pcb.graphics[0] else {
+    panic!("Expected a line, got {:?}", pcb.graphics[0]);
+  };
+  assert_eq!(
+    line.start.to_fixed(),
+    PointFixed {
+      x: 300_000,
+      y: 12_700_000
+    }
+  );
+  assert_eq!(line.start.x, 0.3);
+}
+
+#[test]
+pub fn footprint_lookup() {
+  use kicad_parser::pcb_file::parse_pcb_file;
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (footprint "R" (layer "F.Cu") (uuid "fp-1")
+        (property "Reference" "R1") (property "Value" "10k"))
+      (footprint "R" (layer "F.Cu") (uuid "fp-2")
+        (fp_text reference "R2" (at 0 0) (layer "F.SilkS"))
+        (fp_text value "10k" (at 0 0) (layer "F.Fab")))
+      (footprint "C" (layer "F.Cu") (uuid "fp-3")
+        (property "Reference" "C1") (property "Value" "100n"))
+    )"#,
+  )
+  .unwrap();
+
+  let uuid = |footprint: &kicad_parser::common::Footprint| footprint.uuid.clone().unwrap().0;
+  assert_eq!(
+    pcb.footprint_by_reference("R2").map(uuid).as_deref(),
+    Some("fp-2")
+  );
+  assert_eq!(
+    pcb.footprint_by_reference("C1").map(uuid).as_deref(),
+    Some("fp-3")
+  );
+  assert!(pcb.footprint_by_reference("U3").is_none());
+
+  let tens: Vec<String> = pcb
+    .footprints_by_value("10k")
+    .into_iter()
+    .map(uuid)
+    .collect();
+  assert_eq!(tens, vec!["fp-1", "fp-2"]);
+  assert!(pcb.footprints_by_value("1M").is_empty());
+}
+
+#[test]
+pub fn all_graphics() {
+  use kicad_parser::{common::Point, pcb_file::parse_pcb_file};
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator pcbnew)
+      (footprint "R" (layer "F.Cu") (at 10 20 90)
+        (fp_line (start 1 0) (end 2 0) (stroke (width 0.1) (type solid)) (layer "F.SilkS")))
+      (footprint "C" (layer "F.Cu")
+        (fp_circle (center 0 0) (end 1 0) (stroke (width 0.1) (type solid)) (layer "F.SilkS")))
+      (gr_line (start 0 0) (end 5 5) (stroke (width 0.1) (type solid)) (layer "Edge.Cuts"))
+    )"#,
+  )
+  .unwrap();
+
+  let graphics: Vec<_> = pcb.all_graphics().collect();
+  assert_eq!(graphics.len(), 3);
+  assert_eq!(graphics[0].0.layer(), "Edge.Cuts");
+  assert_eq!(
+    graphics[0].1.transform_point((5.0, 5.0)),
+    Point::new(5.0, 5.0)
+  );
+
+  let (line, placement) = graphics[1];
+  assert_eq!(line.layer(), "F.SilkS");
+  let start = placement.transform_point((1.0, 0.0));
+  assert!((start.x - 10.0).abs() < 1e-9 && (start.y - 19.0).abs() < 1e-9);
+
+  assert_eq!(
+    graphics[2].1.transform_point((1.0, 0.0)),
+    Point::new(1.0, 0.0)
+  );
+  assert_eq!(
+    pcb.all_graphics().count(),
+    pcb.graphics.len()
+      + pcb
+        .footprints
+        .iter()
+        .map(|f| f.graphics.len())
+        .sum::<usize>()
+  );
+}
+
+#[test]
+pub fn paper() {
+  use kicad_parser::{
+    common::Paper,
+    pcb_file::{parse_pcb_file, write_pcb_file},
+  };
+  let paper = |paper: &str| {
+    let pcb = parse_pcb_file(&format!(
+      "(kicad_pcb (version 20240108) (generator pcbnew) {paper})"
+    ))
+    .unwrap();
+    assert_eq!(
+      parse_pcb_file(&write_pcb_file(&pcb)).unwrap().paper,
+      pcb.paper
+    );
+    pcb.paper
+  };
+
+  let a4 = paper(r#"(paper "A4")"#);
+  assert_eq!(a4.size, "A4");
+  assert!(!a4.portrait);
+  assert_eq!(a4.dimensions(), Some((297.0, 210.0)));
+
+  let portrait = paper(r#"(paper "USLetter" portrait)"#);
+  assert!(portrait.portrait);
+  assert_eq!(portrait.dimensions(), Some((215.9, 279.4)));
+
+  assert_eq!(
+    paper(r#"(paper "User" 431.8 100)"#),
+    Paper {
+      size: "User".to_string(),
+      custom_size: Some((431.8, 100.0)),
+      portrait: false,
+    }
+  );
+  assert_eq!(paper("(paper A3)").dimensions(), Some((420.0, 297.0)));
+  assert_eq!(paper(r#"(paper "Custom")"#).dimensions(), None);
+}
+
+#[test]
+pub fn embedded_files() {
+  use kicad_parser::{
+    common::EmbeddedFileType,
+    pcb_file::{parse_pcb_file, write_pcb_file},
+  };
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20241229) (generator "pcbnew")
+  (embedded_fonts no)
+  (embedded_files
+    (file
+      (name "model.step")
+      (type model)
+      (data |SVNPLTEwMzAzLTIx
+        OwpFTkQt|)
+      (checksum "1B2C3D4E")
+    )
+  )
+)"#,
+  )
+  .unwrap();
+
+  let [file] = pcb.embedded_files.as_slice() else {
+    panic!("expected one embedded file, got {:?}", pcb.embedded_files);
+  };
+  assert_eq!(file.name, "model.step");
+  assert_eq!(file.file_type, EmbeddedFileType::Model);
+  assert_eq!(file.data, b"ISO-10303-21;\nEND-");
+  assert_eq!(file.checksum.as_deref(), Some("1B2C3D4E"));
+  assert_eq!(pcb.embedded_fonts, Some(false));
+
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+}
+
+#[test]
+pub fn parse_warnings() {
+  use kicad_parser::{
+    parser::{ParseOptions, ParserErrorKind},
+    pcb_file::{parse_pcb_file, parse_pcb_file_with_warnings},
+  };
+  let input = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+  (footprint "R" (layer "F.Cu")
+    (sheetname "Root")
+  )
+  (footprint "C" (layer "F.Cu"))
+  (target plus (at 10 10) (size 5) (width 0.1) (layer "Edge.Cuts"))
+)"#;
+
+  let (pcb_file, warnings) = parse_pcb_file_with_warnings(input, ParseOptions::default()).unwrap();
+  assert_eq!(pcb_file, parse_pcb_file(input).unwrap());
+  assert_eq!(warnings.len(), 2);
+  assert!(
+    warnings
+      .iter()
+      .all(|warning| warning.kind == ParserErrorKind::Unexpected)
+  );
+  let mut found: Vec<_> = warnings.iter().map(|warning| &warning.found).collect();
+  found.sort();
+  assert!(found[0].contains("sheetname"));
+  assert!(found[1].contains("target"));
+
+  let collect = ParseOptions {
+    collect_unknown: true,
+    ..Default::default()
+  };
+  let (_, warnings) = parse_pcb_file_with_warnings(input, collect).unwrap();
+  assert!(warnings.is_empty());
+
+  // Warnings are only gathered for the parse that asked for them
+  let (_, warnings) =
+    parse_pcb_file_with_warnings("(kicad_pcb (version 20240108))", ParseOptions::default())
+      .unwrap();
+  assert!(warnings.is_empty());
+}
+
+#[test]
+pub fn keepout_zone_round_trip() {
+  use kicad_parser::{
+    common::{FillType, ZoneKeepout, ZoneSmoothing},
+    parser::ParseOptions,
+    pcb_file::{parse_pcb_file, parse_pcb_file_with, parse_pcb_file_with_warnings, write_pcb_file},
+    sexpr::{SExpr, parse_sexpr},
+  };
+
+  let pcb = parse_pcb_file(
+    r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (zone (net 0) (net_name "") (layers "F.Cu" "B.Cu") (uuid "8d2b7c1e-0000-0000-0000-000000000001")
+    (name "no vias") (hatch edge 0.5) (priority 2)
+    (connect_pads (clearance 0))
+    (min_thickness 0.25)
+    (filled_areas_thickness no)
+    (keepout (tracks allowed) (vias not_allowed) (pads allowed) (copperpour not_allowed) (footprints allowed))
+    (fill (thermal_gap 0.5) (thermal_bridge_width 0.5))
+    (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10)))
+  )
+)"#,
+  )
+  .unwrap();
+
+  let zone = &pcb.zones[0];
+  assert_eq!(zone.priority, Some(2));
+  assert_eq!(
+    zone.keepout,
+    Some(ZoneKeepout {
+      allow_tracks: true,
+      allow_vias: false,
+      allow_pads: true,
+      allow_copperpour: false,
+      allow_footprints: true,
+    })
+  );
+  assert_eq!(zone.filled_areas_thickness, Some(false));
+  assert_eq!(zone.fill, Some(FillType::None));
+  assert_eq!(zone.fill_settings.thermal_gap, Some(0.5));
+  assert_eq!(zone.fill_settings.thermal_bridge_width, Some(0.5));
+  assert!(zone.other.is_empty());
+
+  assert_eq!(parse_pcb_file(&write_pcb_file(&pcb)).unwrap(), pcb);
+
+  let unknown = r#"(kicad_pcb (version 20240108) (generator "pcbnew")
+  (zone (net 0) (net_name "") (layer "F.Cu") (attr (teardrop (type padvia)))
+    (fill yes (thermal_gap 0.5) (smoothing fillet) (radius 1) (island_removal_mode 2) (island_area_min 10))
+    (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10)))
+  )
+)"#;
+  let (pcb, warnings) = parse_pcb_file_with_warnings(unknown, ParseOptions::default()).unwrap();
+  let zone = &pcb.zones[0];
+  assert_eq!(zone.fill, Some(FillType::Solid));
+  assert_eq!(zone.fill_settings.smoothing, Some(ZoneSmoothing::Fillet));
+  assert_eq!(zone.fill_settings.island_removal_mode, Some(2));
+  assert!(zone.other.is_empty());
+  assert_eq!(warnings.len(), 1);
+  assert!(warnings[0].found.contains("attr"));
+
+  let strict = ParseOptions {
+    strict: true,
+    ..Default::default()
+  };
+  assert!(parse_pcb_file_with(unknown, strict).is_err());
+
+  let collect = ParseOptions {
+    collect_unknown: true,
+    ..Default::default()
+  };
+  let pcb = parse_pcb_file_with(unknown, collect).unwrap();
+  assert_eq!(
+    pcb.zones[0].other,
+    vec![SExpr::List(
+      parse_sexpr("(attr (teardrop (type padvia)))").unwrap()
+    )]
+  );
+  assert!(write_pcb_file(&pcb).contains("(teardrop"));
+  assert_eq!(
+    parse_pcb_file_with(&write_pcb_file(&pcb), collect).unwrap(),
+    pcb
+  );
+}
@@ -0,0 +1,204 @@
+use kicad_parser::{
+  common::*,
+  sexpr::{ToSExpr, parse_sexpr},
+};
+
+fn parse<T: TryFrom<kicad_parser::sexpr::SExpr, Error = kicad_parser::parser::ParserError>>(
+  input: &str,
+) -> T {
+  parse_sexpr(input).unwrap().as_sexpr_into().unwrap()
+}
+
+#[test]
+pub fn point_list_round_trip_with_arcs() {
+  let points: PointList = parse(
+    "(pts (xy 0 0) (xy 1 0) (arc (start 1 0) (mid 2 1) (end 3 0)) (xy 4 0) (arc (start 4 0) (mid 5 -1) (end 6 0)))",
+  );
+  assert_eq!(points.0.len(), 5);
+  assert_eq!(
+    points.0[2],
+    PointItem::Arc(Arc {
+      start: Point::new(1., 0.),
+      mid: Point::new(2., 1.),
+      end: Point::new(3., 0.),
+    })
+  );
+
+  let round_trip: PointList = points.to_sexpr().try_into().unwrap();
+  assert_eq!(points, round_trip);
+}
+
+#[test]
+pub fn bounding_box_expanded() {
+  let bbox = BoundingBox {
+    min_x: 0.,
+    min_y: 0.,
+    max_x: 10.,
+    max_y: 10.,
+  };
+
+  let expanded = bbox.expanded(1.);
+  assert_eq!((expanded.width(), expanded.height()), (12., 12.));
+  assert_eq!((expanded.x(), expanded.y()), (-1., -1.));
+
+  let inset = bbox.inset(1.);
+  assert_eq!((inset.width(), inset.height()), (8., 8.));
+
+  assert!(BoundingBox::default().expanded(1.).is_empty());
+  assert!(BoundingBox::default().inset(1.).is_empty());
+}
+
+#[test]
+pub fn fixed_point_comparison() {
+  let a: PointList = parse("(pts (xy 0.3 1) (xy 12.7 -5.08))");
+  let b: PointList = parse("(pts (xy 0.30000000000000004 1.000) (xy 12.70000001e0 -5.0800))");
+  assert_ne!(a, b);
+
+  let fixed = |points: &PointList| -> Vec<PointFixed> {
+    points
+      .0
+      .iter()
+      .filter_map(|item| match item {
+        PointItem::Point(point) => Some(point.to_fixed()),
+        PointItem::Arc(_) => None,
+      })
+      .collect()
+  };
+  assert_eq!(fixed(&a), fixed(&b));
+  assert_eq!(
+    fixed(&a)[1],
+    PointFixed {
+      x: 12_700_000,
+      y: -5_080_000
+    }
+  );
+  assert_eq!(fixed(&a)[1].to_point(), Point::new(12.7, -5.08));
+}
+
+#[test]
+pub fn transform_rotations() {
+  let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9;
+  let local = Position {
+    x: 1.,
+    y: 2.,
+    angle: Some(30.),
+  };
+
+  let cases = [
+    (None, (11., 22.), (1., 2.)),
+    (Some(0.), (11., 22.), (1., 2.)),
+    (Some(90.), (12., 19.), (2., -1.)),
+    (Some(180.), (9., 18.), (-1., -2.)),
+  ];
+  for (angle, absolute, rotated) in cases {
+    let anchor = Position {
+      x: 10.,
+      y: 20.,
+      angle,
+    };
+
+    let placed = anchor.transform_position(&local);
+    assert!(
+      close((placed.x, placed.y), absolute),
+      "{angle:?}: {placed:?}"
+    );
+    assert_eq!(placed.angle, Some(30.));
+
+    let vector = anchor.transform_angle((1., 2.));
+    assert!(close(vector.as_tuple(), rotated), "{angle:?}: {vector:?}");
+
+    // The rotation is the same one used for positions, without the translation
+    let origin = Position {
+      angle,
+      ..Default::default()
+    }
+    .transform_position(&local);
+    assert!(close((origin.x, origin.y), vector.as_tuple()));
+  }
+}
+
+#[test]
+pub fn bounding_box_from_points() {
+  let bbox = BoundingBox::from_points([
+    Point::new(1., 2.),
+    Point::new(5., 2.),
+    Point::new(5., 8.),
+    Point::new(1., 8.),
+  ]);
+  assert_eq!((bbox.x(), bbox.y()), (1., 2.));
+  assert_eq!((bbox.width(), bbox.height()), (4., 6.));
+  assert!(BoundingBox::from_points([]).is_empty());
+
+  let other = BoundingBox::from_points([Point::new(3., -1.), Point::new(9., 4.)]);
+  let union: BoundingBox = [bbox.clone(), other.clone()].into_iter().collect();
+  assert_eq!(union, bbox.union(&other));
+  assert_eq!((union.x(), union.y()), (1., -1.));
+  assert_eq!((union.width(), union.height()), (8., 9.));
+
+  let intersection = bbox.intersection(&other).unwrap();
+  assert_eq!((intersection.x(), intersection.y()), (3., 2.));
+  assert_eq!((intersection.width(), intersection.height()), (2., 2.));
+
+  let far = BoundingBox::from_points([Point::new(20., 20.)]);
+  assert!(bbox.intersection(&far).is_none());
+  assert!(
+    std::iter::empty::<BoundingBox>()
+      .collect::<BoundingBox>()
+      .is_empty()
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn serialized_precision() {
+  let point = Point::new(0.1 + 0.2, 12.345_678_9);
+  assert_eq!(
+    serde_json::to_string(&point).unwrap(),
+    r#"{"x":0.3,"y":12.345679}"#
+  );
+
+  let position = Position {
+    x: 1. / 3.,
+    y: -2.5,
+    angle: Some(90.000_000_01),
+  };
+  assert_eq!(
+    serde_json::to_string(&position).unwrap(),
+    r#"{"x":0.333333,"y":-2.5,"angle":90.0}"#
+  );
+
+  let position = Position::default();
+  assert_eq!(
+    serde_json::to_string(&position).unwrap(),
+    r#"{"x":0.0,"y":0.0,"angle":null}"#
+  );
+}
+
+#[test]
+pub fn bounding_box_overlap() {
+  let bbox = BoundingBox::from_points([Point::new(0., 0.), Point::new(4., 2.)]);
+  let overlapping = BoundingBox::from_points([Point::new(3., 1.), Point::new(6., 5.)]);
+  let touching = BoundingBox::from_points([Point::new(4., 2.), Point::new(5., 3.)]);
+  let apart = BoundingBox::from_points([Point::new(5., 0.), Point::new(6., 1.)]);
+
+  assert!(bbox.intersects(&overlapping) && overlapping.intersects(&bbox));
+  assert!(bbox.intersects(&touching));
+  assert!(!bbox.intersects(&apart));
+  assert_eq!(
+    bbox
+      .intersection(&touching)
+      .map(|shared| (shared.width(), shared.height())),
+    Some((0., 0.))
+  );
+
+  assert!(bbox.contains_point(2., 1.));
+  assert!(bbox.contains_point(4., 0.));
+  assert!(!bbox.contains_point(4.1, 1.));
+  assert!(!bbox.contains_point(2., -0.1));
+
+  let empty = BoundingBox::default();
+  assert!(!empty.intersects(&bbox) && !bbox.intersects(&empty));
+  assert!(!empty.intersects(&empty));
+  assert!(!empty.contains_point(0., 0.));
+  assert!(empty.intersection(&bbox).is_none());
+}
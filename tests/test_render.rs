@@ -0,0 +1,54 @@
+#![cfg(feature = "svg")]
+
+use kicad_parser::{
+  pcb_file::parse_pcb_file,
+  render::{RenderStyle, render_layer, render_layer_with},
+};
+
+const BOARD: &str = r#"(kicad_pcb (version 20240108) (generator pcbnew)
+  (footprint "R" (layer "F.Cu") (at 10 10 90)
+    (fp_line (start -1 -1) (end 1 -1) (stroke (width 0.12) (type solid)) (layer "F.SilkS"))
+    (pad "1" smd roundrect (at -0.5 0 90) (size 0.6 0.5) (layers "F.Cu" "F.Mask" "F.Paste")
+      (roundrect_rratio 0.25))
+    (pad "2" thru_hole circle (at 0.5 0 90) (size 0.8 0.8) (drill 0.4) (layers "*.Cu" "*.Mask")))
+  (gr_rect (start 0 0) (end 20 20) (stroke (width 0.05) (type solid)) (fill no) (layer "Edge.Cuts"))
+  (gr_circle (center 5 5) (end 6 5) (stroke (width 0.2) (type solid)) (fill solid) (layer "F.Cu"))
+  (gr_arc (start 15 5) (mid 16 6) (end 17 5) (stroke (width 0.2) (type solid)) (layer "F.Cu"))
+  (gr_poly (pts (xy 2 15) (xy 4 15) (xy 4 17)) (stroke (width 0) (type solid)) (fill solid) (layer "B.Cu"))
+)"#;
+
+#[test]
+pub fn render_copper_layers() {
+  let pcb = parse_pcb_file(BOARD).unwrap();
+
+  let front = render_layer(&pcb, "F.Cu").to_string();
+  assert!(front.contains(r#"viewBox="0 0 20 20""#), "{front}");
+  assert_eq!(front.matches("<circle").count(), 2, "{front}");
+  assert_eq!(front.matches("<rect").count(), 1, "{front}");
+  assert_eq!(front.matches("<path").count(), 1, "{front}");
+  assert!(front.contains(r##"fill="#c83434""##));
+
+  // Only the through-hole pad and the polygon, drawn with the default stroke width
+  let back = render_layer_with(
+    &pcb,
+    "B.Cu",
+    &RenderStyle {
+      color: "blue".to_string(),
+      pad_color: "gold".to_string(),
+      default_stroke_width: 0.3,
+    },
+  )
+  .to_string();
+  assert_eq!(back.matches("<circle").count(), 1, "{back}");
+  assert!(back.contains(r#"fill="gold""#), "{back}");
+  assert!(back.contains(r#"stroke-width="0.3""#), "{back}");
+  assert!(!back.contains("<rect"), "{back}");
+
+  let silk = render_layer(&pcb, "F.SilkS").to_string();
+  assert_eq!(silk.matches("<line").count(), 1, "{silk}");
+  // The footprint line is rotated with the footprint
+  assert!(
+    silk.contains(r#"x1="9""#) && silk.contains(r#"y1="11""#),
+    "{silk}"
+  );
+}
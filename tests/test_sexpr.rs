@@ -0,0 +1,264 @@
+use kicad_parser::{
+  common::Position,
+  sexpr::{SExpr, SExprEvent, SExprList, SExprReader, SExprRef, parse_sexpr, parse_sexpr_borrowed},
+};
+
+const CONTENT: &str = include_str!("./MainBoard.kicad_pcb");
+
+#[test]
+pub fn non_finite_numbers_are_rejected() {
+  let list = parse_sexpr("(at inf 0)").unwrap();
+  assert!(matches!(list.0[1], SExpr::Float(f) if f.is_infinite()));
+  assert!(list.as_sexpr_into::<Position>().is_err());
+
+  let list = parse_sexpr("(at 1 nan)").unwrap();
+  assert!(list.as_sexpr_into::<Position>().is_err());
+
+  let list = parse_sexpr("(at 1 2)").unwrap();
+  assert!(list.as_sexpr_into::<Position>().is_ok());
+}
+
+#[test]
+pub fn borrowed_parse_matches_owned() {
+  let owned = parse_sexpr(CONTENT).unwrap();
+  let borrowed = parse_sexpr_borrowed(CONTENT).unwrap();
+  assert_eq!(borrowed.name(), Some("kicad_pcb"));
+  assert_eq!(borrowed.to_owned_list(), owned);
+
+  let footprints = borrowed.lists_named("footprint").count();
+  assert!(footprints > 0);
+  assert!(matches!(
+    borrowed.lists_named("footprint").next().unwrap().0[1],
+    SExprRef::Value(_)
+  ));
+}
+
+#[test]
+pub fn write_formatting() {
+  let list = parse_sexpr(
+    r#"(fp_line (start 0 -1.5) (end 2.5 0) (stroke (width 0.12) (type solid)) (layer "F.SilkS"))"#,
+  )
+  .unwrap();
+
+  let mut out = String::new();
+  list.write(&mut out, 0);
+  assert_eq!(
+    out,
+    "(fp_line\n\t(start 0 -1.5)\n\t(end 2.5 0)\n\t(stroke\n\t\t(width 0.12)\n\t\t(type solid)\n\t)\n\t(layer \"F.SilkS\")\n)"
+  );
+
+  let mut out = String::new();
+  SExpr::value("say \"hi\"").write(&mut out, 0);
+  assert_eq!(out, r#""say \"hi\"""#);
+}
+
+#[test]
+pub fn list_consumption() {
+  let mut list = SExprList::from(vec![
+    SExpr::symbol("at"),
+    SExpr::Float(1.0),
+    SExpr::Float(2.0),
+  ]);
+  assert_eq!(list.peek_name().unwrap(), "at");
+  assert_eq!(list.discard(1).unwrap().next_into::<f64>().unwrap(), 1.0);
+  assert_eq!(list.next_maybe(), Some(SExpr::Float(2.0)));
+  assert_eq!(list.next_maybe(), None);
+  assert!(list.expect_end().is_ok());
+}
+
+#[test]
+pub fn oversized_hex_is_an_error() {
+  assert!(parse_sexpr("(layerselection 0xFFFFFFFFFFFFFFFFFF)").is_err());
+
+  let list = parse_sexpr("(layerselection 0x00000000_00000000_55555555_5755f5ff)").unwrap();
+  assert_eq!(list.0[1], SExpr::Hex(0x55555555_5755f5ff));
+
+  let list = parse_sexpr("(name 0xyz 0x1g 0x1f)").unwrap();
+  assert_eq!(list.0[1], SExpr::symbol("0xyz"));
+  assert_eq!(list.0[2], SExpr::symbol("0x1g"));
+  assert_eq!(list.0[3], SExpr::Hex(0x1f));
+}
+
+#[test]
+pub fn escaped_strings() {
+  let list = parse_sexpr(r#"(property "Value" "a \"quoted\" word")"#).unwrap();
+  assert_eq!(list.0[2], SExpr::value(r#"a "quoted" word"#));
+
+  let list = parse_sexpr(r#"(gr_text "line1\nline2" (at 0 0))"#).unwrap();
+  assert_eq!(list.0[1], SExpr::value("line1\nline2"));
+
+  let mut out = String::new();
+  list.0[1].write(&mut out, 0);
+  assert_eq!(out, r#""line1\nline2""#);
+}
+
+#[test]
+pub fn read_yes_no() {
+  let flag = |input: &str| parse_sexpr(input).unwrap().read_yes_no();
+
+  assert!(flag("(hide yes)").unwrap());
+  assert!(!flag("(hide no)").unwrap());
+  assert!(flag("(hide true)").unwrap());
+  assert!(!flag("(hide false)").unwrap());
+  assert!(flag("(hide maybe)").is_err());
+  assert!(flag("(hide \"yes\")").is_err());
+  assert!(flag("(hide yes no)").is_err());
+  assert!(flag("(hide)").is_err());
+}
+
+#[test]
+pub fn streaming_reader_events() {
+  let events: Vec<_> = SExprReader::new(r#"(at 1 "two" (xy 3) () (0x4 five))"#)
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert_eq!(
+    events,
+    vec![
+      SExprEvent::ListStart(Some("at")),
+      SExprEvent::Atom(SExpr::Int(1)),
+      SExprEvent::Atom(SExpr::value("two")),
+      SExprEvent::ListStart(Some("xy")),
+      SExprEvent::Atom(SExpr::Int(3)),
+      SExprEvent::ListEnd,
+      SExprEvent::ListStart(None),
+      SExprEvent::ListEnd,
+      SExprEvent::ListStart(None),
+      SExprEvent::Atom(SExpr::Hex(4)),
+      SExprEvent::Atom(SExpr::symbol("five")),
+      SExprEvent::ListEnd,
+      SExprEvent::ListEnd,
+    ]
+  );
+
+  for broken in ["", "(at 1", "(at 1))", "(a) (b)", "at", "(at 1.5mm)"] {
+    let result: Result<Vec<_>, _> = SExprReader::new(broken).collect();
+    assert!(result.is_err(), "{broken:?} should fail");
+  }
+}
+
+#[test]
+pub fn streaming_reader_matches_tree() {
+  let owned = parse_sexpr(CONTENT).unwrap();
+  let expected: Vec<_> = owned
+    .0
+    .iter()
+    .filter_map(|expr| match expr {
+      SExpr::List(list) if list.peek_name().ok() == Some("footprint") => Some(list.clone()),
+      _ => None,
+    })
+    .collect();
+
+  let mut reader = SExprReader::new(CONTENT);
+  let mut footprints = vec![];
+  while let Some(event) = reader.next() {
+    match event.unwrap() {
+      SExprEvent::ListStart(Some("footprint")) if reader.depth() == 2 => {
+        footprints.push(reader.finish_list(Some("footprint")).unwrap());
+      }
+      SExprEvent::ListStart(_) if reader.depth() == 2 => reader.skip_list().unwrap(),
+      _ => {}
+    }
+  }
+
+  assert_eq!(reader.depth(), 0);
+  assert!(!footprints.is_empty());
+  assert_eq!(footprints, expected);
+}
+
+#[test]
+pub fn integers_keep_their_type() {
+  let list = parse_sexpr("(version 20240101)").unwrap();
+  assert!(matches!(list.0[1], SExpr::Int(20240101)));
+
+  let mut out = String::new();
+  list.write(&mut out, 0);
+  assert_eq!(out, "(version 20240101)");
+
+  let list = parse_sexpr("(at 12 -3 1.5 2e3 12.000000)").unwrap();
+  assert!(matches!(list.0[1], SExpr::Int(12)));
+  assert!(matches!(list.0[2], SExpr::Int(-3)));
+  assert!(matches!(list.0[3], SExpr::Float(_)));
+  assert!(matches!(list.0[4], SExpr::Float(_)));
+  assert!(matches!(list.0[5], SExpr::Float(_)));
+  assert_eq!(list.0[5], SExpr::Int(12));
+
+  // Eight character exponents are numbers, not legacy hex timestamps
+  let list = parse_sexpr("(at 12345e67 1e100000 5F68FEEE)").unwrap();
+  assert_eq!(list.0[1], SExpr::Float(12345e67));
+  assert!(matches!(list.0[2], SExpr::Float(f) if f.is_infinite()));
+  assert_eq!(list.0[3], SExpr::symbol("5F68FEEE"));
+}
+
+#[test]
+pub fn out_of_range_integers_are_rejected() {
+  assert_eq!(u32::try_from(SExpr::Int(7)).unwrap(), 7);
+  assert!(u32::try_from(SExpr::Int(-1)).is_err());
+  assert!(u32::try_from(SExpr::Int(1 << 32)).is_err());
+  assert!(u8::try_from(SExpr::Int(-1)).is_err());
+  assert!(u8::try_from(SExpr::Int(256)).is_err());
+  assert!(u8::try_from(SExpr::Hex(0x1ff)).is_err());
+  assert_eq!(i32::try_from(SExpr::Int(-5)).unwrap(), -5);
+  assert!(i32::try_from(SExpr::Int(i64::from(i32::MAX) + 1)).is_err());
+
+  // Floats convert only when they hold a whole number in range
+  assert_eq!(u32::try_from(SExpr::Float(3.0)).unwrap(), 3);
+  assert!(u32::try_from(SExpr::Float(-1.0)).is_err());
+  assert!(u8::try_from(SExpr::Float(300.0)).is_err());
+  assert!(i32::try_from(SExpr::Float(1.5)).is_err());
+  assert!(i64::try_from(SExpr::Float(1e30)).is_err());
+  assert!(i64::try_from(SExpr::Float(f64::NAN)).is_err());
+}
+
+#[test]
+pub fn get_named_children() {
+  let mut list = parse_sexpr(
+    r#"(pad "1" smd (layer "F.Cu") (at 1 2) ("layer" "quoted") (layer "B.Cu") ((layer nested)))"#,
+  )
+  .unwrap();
+
+  let layer = list.get("layer").unwrap();
+  assert_eq!(layer.0[1], SExpr::value("F.Cu"));
+  assert_eq!(list.get_all("layer").len(), 2);
+  assert!(list.get("pad").is_none());
+  assert!(list.get("size").is_none());
+
+  // Lookups leave the cursor alone
+  assert_eq!(list.peek_name().unwrap(), "pad");
+  list.discard(3).unwrap();
+  assert_eq!(list.get("at").unwrap().0.len(), 3);
+}
+
+#[test]
+pub fn blobs() {
+  let input = "(data |KLUv/QBY\n  AQAA|)";
+  let list = parse_sexpr(input).unwrap();
+  assert_eq!(list.0[1], SExpr::Blob("KLUv/QBY\n  AQAA".to_string()));
+  assert_eq!(
+    parse_sexpr_borrowed(input).unwrap().0[1],
+    SExprRef::Blob("KLUv/QBY\n  AQAA")
+  );
+
+  let mut out = String::new();
+  list.write(&mut out, 0);
+  assert_eq!(parse_sexpr(&out).unwrap(), list);
+
+  let events: Vec<_> = SExprReader::new(input).collect::<Result<_, _>>().unwrap();
+  assert_eq!(
+    events[1],
+    SExprEvent::Atom(SExpr::Blob("KLUv/QBY\n  AQAA".to_string()))
+  );
+
+  assert!(parse_sexpr("(data |KLUv)").is_err());
+}
+
+#[test]
+pub fn display_formatting() {
+  let net = parse_sexpr(r#"(net 0 "GND")"#).unwrap();
+  assert_eq!(net.to_string(), r#"(net 0 "GND")"#);
+
+  let list = parse_sexpr("(stroke (width 0.120000) (type solid) (angle 90.0))").unwrap();
+  assert_eq!(
+    SExpr::List(list).to_string(),
+    "(stroke\n  (width 0.12)\n  (type solid)\n  (angle 90)\n)"
+  );
+}